@@ -54,19 +54,23 @@ const MAX_FELTS_IN_CALLDATA: &str = "30000";
 
 /// Inspired by the Import command from Reth.
 /// https://github.com/paradigmxyz/reth/blob/main/bin/reth/src/commands/import.rs
-#[tokio::main]
-async fn main() -> eyre::Result<()> {
+fn main() -> eyre::Result<()> {
     let args = Args::parse();
 
-    // Get the provider
-    let provider = JsonRpcClient::new(HttpTransport::new(Url::from_str(STARKNET_RPC_URL)?));
-    let starknet_provider = StarknetProvider::new(provider);
-
-    // Set the env
+    // Set the env before starting the runtime, while no other thread reads it
     std::env::set_var("RELAYER_PRIVATE_KEY", format!("0x{:x}", args.relayer_pk));
     std::env::set_var("MAX_FELTS_IN_CALLDATA", MAX_FELTS_IN_CALLDATA);
     std::env::set_var("STARKNET_NETWORK", STARKNET_RPC_URL);
 
+    tokio::runtime::Builder::new_multi_thread().enable_all().build()?.block_on(import(args))
+}
+
+/// Relays the transactions of the blocks of the chain file.
+async fn import(args: Args) -> eyre::Result<()> {
+    // Get the provider
+    let provider = JsonRpcClient::new(HttpTransport::new(Url::from_str(STARKNET_RPC_URL)?));
+    let starknet_provider = StarknetProvider::new(provider);
+
     // Prepare the relayer
     let relayer_balance = starknet_provider.balance_at(args.relayer_address, BlockId::Tag(BlockTag::Latest)).await?;
     let relayer_balance = into_via_try_wrapper!(relayer_balance)?;
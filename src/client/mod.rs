@@ -5,21 +5,21 @@ use crate::{
         validate::KakarotTransactionValidatorBuilder,
     },
     providers::{
+        cache_provider::{CacheConfig, CacheMetrics, CachedEthProvider},
         eth_provider::{
             database::{
-                filter,
-                filter::EthDatabaseFilterBuilder,
-                types::transaction::{ExtendedTransaction, StoredEthStarknetTransactionHash},
+                ethereum::EthereumTransactionStore,
+                types::{receipt::ExtendedTxReceipt, transaction::ExtendedTransaction},
                 Database,
             },
             error::SignatureError,
             provider::{EthApiResult, EthDataProvider},
-            TransactionProvider, TxPoolProvider,
+            ReceiptProvider, TransactionProvider, TxPoolProvider,
         },
         sn_provider::StarknetProvider,
     },
 };
-use alloy_eips::eip2718::Encodable2718;
+use alloy_eips::{eip2718::Encodable2718, BlockId};
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rlp::Decodable;
 use alloy_rpc_types_txpool::TxpoolContent;
@@ -33,7 +33,7 @@ use reth_transaction_pool::{
     blobstore::NoopBlobStore, AllPoolTransactions, EthPooledTransaction, PoolConfig, PoolTransaction,
     TransactionOrigin, TransactionPool,
 };
-use starknet::providers::Provider;
+use starknet::{core::types::Felt, providers::Provider};
 use std::{collections::BTreeMap, sync::Arc};
 
 #[async_trait]
@@ -46,13 +46,19 @@ pub trait KakarotTransactions {
 pub trait TransactionHashProvider {
     /// Returns the transaction by hash.
     async fn transaction_by_hash(&self, hash: B256) -> EthApiResult<Option<ExtendedTransaction>>;
+
+    /// Returns the receipt of the transaction, with the Starknet transaction hash and block number.
+    async fn transaction_receipt(&self, hash: B256) -> EthApiResult<Option<ExtendedTxReceipt>>;
+
+    /// Returns the receipts of the block, with the Starknet transaction hashes and block number.
+    async fn block_receipts(&self, block_id: Option<BlockId>) -> EthApiResult<Option<Vec<ExtendedTxReceipt>>>;
 }
 
 /// Provides a wrapper structure around the Ethereum Provider
 /// and the Mempool.
 #[derive(Debug, Clone)]
 pub struct EthClient<SP: Provider + Send + Sync> {
-    eth_provider: EthDataProvider<SP>,
+    eth_provider: CachedEthProvider<EthDataProvider<SP>>,
     pool: Arc<KakarotPool<EthDataProvider<SP>>>,
 }
 
@@ -62,7 +68,7 @@ where
 {
     /// Get the Starknet provider from the Ethereum provider.
     pub const fn starknet_provider(&self) -> &StarknetProvider<SP> {
-        self.eth_provider.starknet_provider()
+        self.eth_provider.inner().starknet_provider()
     }
 
     /// Tries to start a [`EthClient`] by fetching the current chain id, initializing a [`EthDataProvider`] and a [`Pool`].
@@ -84,11 +90,21 @@ where
             pool_config,
         ));
 
-        Self { eth_provider, pool }
+        Self { eth_provider: CachedEthProvider::new(eth_provider, CacheConfig::DISABLED, None), pool }
+    }
+
+    /// Caches the blocks, transactions and receipts read from the [`EthDataProvider`], and the
+    /// state it reads from Starknet.
+    #[must_use]
+    pub fn with_cache(mut self, config: CacheConfig, metrics: Option<CacheMetrics>) -> Self {
+        let eth_provider =
+            self.eth_provider.inner().clone().with_state_cache(config.max_state_entries, config.max_code_entries);
+        self.eth_provider = CachedEthProvider::new(eth_provider, config, metrics);
+        self
     }
 
-    /// Returns a clone of the [`EthDataProvider`]
-    pub const fn eth_provider(&self) -> &EthDataProvider<SP> {
+    /// Returns a clone of the [`EthDataProvider`], wrapped in its cache
+    pub const fn eth_provider(&self) -> &CachedEthProvider<EthDataProvider<SP>> {
         &self.eth_provider
     }
 
@@ -195,23 +211,58 @@ where
             .or(self.eth_provider.transaction_by_hash(hash).await?);
 
         if let Some(ref mut transaction) = tx {
-            // Fetch the Starknet transaction hash if it exists.
-            let filter = EthDatabaseFilterBuilder::<filter::EthStarknetTransactionHash>::default()
-                .with_tx_hash(&transaction.hash)
-                .build();
-
-            let hash_mapping: Option<StoredEthStarknetTransactionHash> =
-                self.eth_provider.database().get_one(filter, None).await?;
-
-            // Add the Starknet transaction hash to the transaction fields.
-            if let Some(hash_mapping) = hash_mapping {
+            // Add the Starknet transaction hash to the transaction fields if it exists.
+            if let Some(starknet_hash) =
+                self.eth_provider.database().starknet_transaction_hash(&transaction.hash).await?
+            {
                 transaction.other.insert(
                     "starknet_transaction_hash".to_string(),
-                    serde_json::Value::String(hash_mapping.hashes.starknet_hash.to_fixed_hex_string()),
+                    serde_json::Value::String(starknet_hash.to_fixed_hex_string()),
                 );
             }
         }
 
         Ok(tx)
     }
+
+    async fn transaction_receipt(&self, hash: B256) -> EthApiResult<Option<ExtendedTxReceipt>> {
+        let mut receipt = self.eth_provider.transaction_receipt(hash).await?;
+
+        if let Some(ref mut receipt) = receipt {
+            let starknet_hash = self.eth_provider.database().starknet_transaction_hash(&hash).await?;
+            add_starknet_receipt_fields(receipt, starknet_hash);
+        }
+
+        Ok(receipt)
+    }
+
+    async fn block_receipts(&self, block_id: Option<BlockId>) -> EthApiResult<Option<Vec<ExtendedTxReceipt>>> {
+        let mut receipts = self.eth_provider.block_receipts(block_id).await?;
+
+        if let Some(ref mut receipts) = receipts {
+            let hashes: Vec<_> = receipts.iter().map(|receipt| receipt.transaction_hash).collect();
+            let starknet_hashes = self.eth_provider.database().starknet_transaction_hashes(&hashes).await?;
+            for receipt in receipts {
+                let starknet_hash = starknet_hashes.get(&receipt.transaction_hash).copied();
+                add_starknet_receipt_fields(receipt, starknet_hash);
+            }
+        }
+
+        Ok(receipts)
+    }
+}
+
+/// Adds the hash of the Starknet transaction which relayed the transaction, if known, and the
+/// number of the Starknet block which included it to the receipt fields. Kakarot blocks have the
+/// number of the Starknet block they're built from, pending blocks don't have a Starknet number.
+fn add_starknet_receipt_fields(receipt: &mut ExtendedTxReceipt, starknet_hash: Option<Felt>) {
+    if let Some(starknet_hash) = starknet_hash {
+        receipt.other.insert(
+            "starknet_transaction_hash".to_string(),
+            serde_json::Value::String(starknet_hash.to_fixed_hex_string()),
+        );
+    }
+    if let Some(number) = receipt.block_number.filter(|_| receipt.block_hash.is_some_and(|hash| !hash.is_zero())) {
+        receipt.other.insert("starknet_block_number".to_string(), serde_json::Value::String(format!("{number:#x}")));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{config::KakarotRpcConfig, eth_rpc::config::RPCConfig};
+use crate::config::{Config, KakarotRpcConfig};
 use num_traits::ToPrimitive;
 use starknet::{
     core::types::{Felt, NonZeroFelt},
@@ -23,12 +23,10 @@ pub static ETH_CHAIN_ID: LazyLock<u64> = LazyLock::new(|| {
 });
 
 /// The Kakarot RPC configuration.
-pub static KAKAROT_RPC_CONFIG: LazyLock<KakarotRpcConfig> =
-    LazyLock::new(|| KakarotRpcConfig::from_env().expect("failed to load Kakarot RPC config"));
-
-/// The RPC configuration.
-pub static RPC_CONFIG: LazyLock<RPCConfig> =
-    LazyLock::new(|| RPCConfig::from_env().expect("failed to load RPC config"));
+pub static KAKAROT_RPC_CONFIG: LazyLock<KakarotRpcConfig> = LazyLock::new(|| {
+    let config = Config::current().expect("failed to read the configuration");
+    KakarotRpcConfig::from_config(&config).expect("the configuration is validated at startup")
+});
 
 /// The gas limit for Kakarot blocks.
 pub const KKRT_BLOCK_GAS_LIMIT: u64 = 7_000_000;
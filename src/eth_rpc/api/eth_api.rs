@@ -1,10 +1,15 @@
-use crate::providers::eth_provider::database::types::receipt::ExtendedTxReceipt;
+use crate::{
+    models::bundle::{CallBundle, CallBundleResponse},
+    providers::eth_provider::database::types::{header::ExtendedBlock, receipt::ExtendedTxReceipt},
+};
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, Bytes, B256, B64, U256, U64};
 use alloy_rpc_types::{
-    serde_helpers::JsonStorageKey, state::StateOverride, AccessListResult, Block, BlockOverrides,
-    EIP1186AccountProofResponse, FeeHistory, Filter, FilterChanges, Index, SyncStatus, Transaction as EthTransaction,
-    TransactionRequest, Work,
+    serde_helpers::JsonStorageKey,
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+    AccessListResult, Block, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Filter,
+    FilterChanges, Index, StateContext, SyncStatus, Transaction as EthTransaction, TransactionRequest, Work,
 };
 use alloy_serde::WithOtherFields;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
@@ -136,6 +141,30 @@ pub trait EthApi {
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes>;
 
+    /// Simulates the blocks of calls on top of the state of the given block, each call being
+    /// executed on top of the state changes of the previous ones.
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock<ExtendedBlock>>>;
+
+    /// Executes the transaction requests of the bundle one after the other on top of the state
+    /// context, each one on top of the state changes of the previous ones.
+    #[method(name = "callMany")]
+    async fn call_many(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<Vec<EthCallResponse>>;
+
+    /// Executes the signed transactions of the bundle one after the other on top of the state
+    /// block, and returns their results and the payments of the bundle to the coinbase.
+    #[method(name = "callBundle")]
+    async fn call_bundle(&self, bundle: CallBundle) -> RpcResult<CallBundleResponse>;
+
     /// Generates an access list for a transaction.
     ///
     /// This method creates an [EIP2930](https://eips.ethereum.org/EIPS/eip-2930) type accessList based on a given Transaction.
@@ -158,9 +187,14 @@ pub trait EthApi {
     ) -> RpcResult<AccessListResult>;
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
-    /// complete.
+    /// complete, optionally on top of the overridden state.
     #[method(name = "estimateGas")]
-    async fn estimate_gas(&self, request: TransactionRequest, block_id: Option<BlockId>) -> RpcResult<U256>;
+    async fn estimate_gas(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<U256>;
 
     /// Returns the current price per gas in wei.
     #[method(name = "gasPrice")]
@@ -241,7 +275,7 @@ pub trait EthApi {
         address: Address,
         keys: Vec<B256>,
         block_id: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse>;
+    ) -> RpcResult<WithOtherFields<EIP1186AccountProofResponse>>;
 
     /// Creates a filter object, based on filter options, to notify when the state changes (logs).
     #[method(name = "newFilter")]
@@ -0,0 +1,18 @@
+use crate::providers::eth_provider::database::types::transaction::ExtendedTransaction;
+use alloy_rpc_types::pubsub::{Params, SubscriptionKind, SubscriptionResult};
+use jsonrpsee::proc_macros::rpc;
+
+/// Ethereum publish-subscribe JSON-RPC API Trait
+/// Based on <https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc-eth-api/src/pubsub.rs>
+#[rpc(server, namespace = "eth")]
+#[async_trait]
+pub trait EthPubSubApi {
+    /// Creates a subscription for the given kind of events. The subscription is
+    /// only available over a WebSocket connection.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = SubscriptionResult<ExtendedTransaction>
+    )]
+    async fn subscribe(&self, kind: SubscriptionKind, params: Option<Params>) -> jsonrpsee::core::SubscriptionResult;
+}
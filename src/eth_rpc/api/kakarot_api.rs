@@ -1,9 +1,45 @@
-use crate::providers::eth_provider::constant::Constant;
+use crate::{
+    models::kakarot::{AccountClassHashes, AccountStatus},
+    providers::eth_provider::constant::Constant,
+};
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types::BlockId;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use starknet::core::types::Felt;
 
 #[rpc(server, namespace = "kakarot")]
 #[async_trait]
 pub trait KakarotApi {
     #[method(name = "getConfig")]
     async fn get_config(&self) -> RpcResult<Constant>;
+
+    /// Returns the hash of the Ethereum transaction relayed by the Starknet transaction, if it was
+    /// relayed by this node.
+    #[method(name = "getEthTransactionHash")]
+    async fn get_eth_transaction_hash(&self, starknet_hash: Felt) -> RpcResult<Option<B256>>;
+
+    /// Returns the hash of the Starknet transaction which relayed the Ethereum transaction, if it
+    /// was relayed by this node.
+    #[method(name = "getStarknetTransactionHash")]
+    async fn get_starknet_transaction_hash(&self, eth_hash: B256) -> RpcResult<Option<Felt>>;
+
+    /// Returns the Starknet address of the account of the EVM address.
+    #[method(name = "getStarknetAddress")]
+    async fn get_starknet_address(&self, address: Address) -> RpcResult<Felt>;
+
+    /// Returns whether the Starknet account of the EVM address is deployed and initialized.
+    #[method(name = "getAccountStatus")]
+    async fn get_account_status(&self, address: Address, block_id: Option<BlockId>) -> RpcResult<AccountStatus>;
+
+    /// Returns the base fee set in the Kakarot contract.
+    #[method(name = "getBaseFee")]
+    async fn get_base_fee(&self, block_id: Option<BlockId>) -> RpcResult<U256>;
+
+    /// Returns the coinbase set in the Kakarot contract.
+    #[method(name = "getCoinbase")]
+    async fn get_coinbase(&self, block_id: Option<BlockId>) -> RpcResult<Address>;
+
+    /// Returns the class hashes of the accounts deployed by the Kakarot contract.
+    #[method(name = "getAccountClassHashes")]
+    async fn get_account_class_hashes(&self, block_id: Option<BlockId>) -> RpcResult<AccountClassHashes>;
 }
@@ -1,6 +1,11 @@
-use alloy_rpc_types::BlockId;
-use alloy_rpc_types_trace::parity::LocalizedTransactionTrace;
+use alloy_primitives::B256;
+use alloy_rpc_types::{BlockId, Index, TransactionRequest};
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    parity::{LocalizedTransactionTrace, TraceResults, TraceResultsWithTransactionHash, TraceType},
+};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use std::collections::HashSet;
 
 /// Trace API
 #[rpc(server, namespace = "trace")]
@@ -9,4 +14,51 @@ pub trait TraceApi {
     /// Returns the parity traces for the given block.
     #[method(name = "block")]
     async fn trace_block(&self, block_id: BlockId) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>>;
+
+    /// Returns the parity traces for the given transaction.
+    #[method(name = "transaction")]
+    async fn trace_transaction(&self, transaction_hash: B256) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>>;
+
+    /// Returns the parity trace at the given position in the traces of the transaction.
+    #[method(name = "get")]
+    async fn trace_get(&self, hash: B256, indices: Vec<Index>) -> RpcResult<Option<LocalizedTransactionTrace>>;
+
+    /// Executes the call on top of the state of the given block and returns the requested traces.
+    #[method(name = "call")]
+    async fn trace_call(
+        &self,
+        request: TransactionRequest,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<TraceResults>;
+
+    /// Executes the calls one after the other on top of the state of the given block and
+    /// returns the requested traces for each of them.
+    #[method(name = "callMany")]
+    async fn trace_call_many(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<TraceResults>>;
+
+    /// Replays the given transaction and returns the requested traces.
+    #[method(name = "replayTransaction")]
+    async fn trace_replay_transaction(
+        &self,
+        transaction_hash: B256,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<TraceResults>;
+
+    /// Replays all the transactions of the given block and returns the requested traces.
+    #[method(name = "replayBlockTransactions")]
+    async fn trace_replay_block_transactions(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<Option<Vec<TraceResultsWithTransactionHash>>>;
+
+    /// Returns the parity traces matching the given filter, over at most 100 blocks. The range
+    /// defaults to the last 100 blocks up to `toBlock`, itself defaulting to the latest block.
+    #[method(name = "filter")]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>>;
 }
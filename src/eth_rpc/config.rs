@@ -1,30 +1,247 @@
+use crate::{config::Config, eth_rpc::rpc::KakarotRpcModule};
 use eyre::{eyre, Result};
+use std::{collections::HashSet, net::IpAddr, path::PathBuf, time::Duration};
 
 #[derive(Debug, Clone)]
 pub struct RPCConfig {
     pub socket_addr: String,
+    /// The rate limits of the clients, no limit if `None`.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// The proxies trusted to forward the IP address of the clients.
+    pub trusted_proxies: Vec<IpAddr>,
+    /// The limits of the connections and requests and the timeout of the calls.
+    pub limits: RequestLimitsConfig,
 }
 
 impl RPCConfig {
     pub const fn new(socket_addr: String) -> Self {
-        Self { socket_addr }
+        Self { socket_addr, rate_limit: None, trusted_proxies: Vec::new(), limits: RequestLimitsConfig::DEFAULT }
     }
 
-    pub fn from_env() -> Result<Self> {
-        let socket_addr = std::env::var("KAKAROT_RPC_URL")
-            .map_err(|_| eyre!("Missing mandatory environment variable: KAKAROT_RPC_URL"))?;
-        Ok(Self::new(socket_addr))
+    /// Returns the configuration of the RPC server.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let socket_addr =
+            config.kakarot_rpc_url.clone().ok_or_else(|| eyre!("Missing kakarot_rpc_url (env KAKAROT_RPC_URL)"))?;
+        Ok(Self {
+            socket_addr,
+            rate_limit: RateLimitConfig::from_config(config)?,
+            trusted_proxies: config.rpc_trusted_proxies.clone().unwrap_or_default(),
+            limits: RequestLimitsConfig::from_config(config),
+        })
     }
+}
 
-    pub fn from_port(port: u16) -> Result<Self> {
-        let mut config = Self::from_env()?;
-        // Remove port from socket address and replace it with provided port
-        let parts: Vec<&str> = config.socket_addr.split(':').collect();
-        if let Some(addr) = parts.first() {
-            config.socket_addr = format!("{addr}:{port}");
+/// Selection of the RPC modules and methods served by the node.
+#[derive(Debug, Clone, Default)]
+pub struct RpcModulesConfig {
+    /// The modules to serve, all of them if `None`.
+    pub modules: Option<HashSet<KakarotRpcModule>>,
+    /// The methods to serve, all the methods of the served modules if `None`.
+    pub allowed_methods: Option<HashSet<String>>,
+    /// The methods never served, even if allowed.
+    pub denied_methods: HashSet<String>,
+}
+
+impl RpcModulesConfig {
+    /// Returns the modules and methods selected by the configuration. Methods can be
+    /// matched by prefix with a trailing `*`, e.g. `debug_*`.
+    pub fn from_config(config: &Config) -> Self {
+        let methods = |methods: &Option<Vec<String>>| -> HashSet<String> {
+            methods.iter().flatten().filter(|method| !method.is_empty()).cloned().collect()
+        };
+        let allowed_methods = Some(methods(&config.rpc_methods_allowlist)).filter(|methods| !methods.is_empty());
+
+        Self {
+            modules: config.rpc_http_api.as_ref().map(|modules| modules.iter().copied().collect()),
+            allowed_methods,
+            denied_methods: methods(&config.rpc_methods_denylist),
         }
-        Ok(config)
     }
+
+    /// Stops serving the given modules, e.g. the ones served by the admin server.
+    pub fn exclude_modules(&mut self, excluded: &HashSet<KakarotRpcModule>) {
+        let modules = self.modules.get_or_insert_with(|| KakarotRpcModule::ALL.into());
+        modules.retain(|module| !excluded.contains(module));
+    }
+
+    /// Returns true if the module should be served.
+    pub fn is_module_enabled(&self, module: KakarotRpcModule) -> bool {
+        self.modules.as_ref().map_or(true, |modules| modules.contains(&module))
+    }
+
+    /// Returns true if the method should be served.
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        let matches = |pattern: &String| method_matches(pattern, method);
+
+        self.allowed_methods.as_ref().map_or(true, |allowed| allowed.iter().any(matches))
+            && !self.denied_methods.iter().any(matches)
+    }
+}
+
+/// Limits of the connections, size limits of the requests and responses and timeout of the calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimitsConfig {
+    /// The maximum number of connections to the server.
+    pub max_connections: u32,
+    /// The maximum number of subscriptions of a WebSocket connection.
+    pub max_subscriptions_per_connection: u32,
+    /// The maximum number of calls in a batch, unlimited if `None`.
+    pub max_batch_len: Option<u32>,
+    /// The maximum size in bytes of the body of a request.
+    pub max_request_body_size: u32,
+    /// The maximum size in bytes of the response to a call.
+    pub max_response_body_size: u32,
+    /// The maximum duration of a call, unlimited if `None`.
+    pub call_timeout: Option<Duration>,
+}
+
+impl RequestLimitsConfig {
+    /// Default limits: 100 connections of at most 1024 subscriptions, unlimited batches and bodies
+    /// of at most 10 MiB.
+    pub const DEFAULT: Self = Self {
+        max_connections: 100,
+        max_subscriptions_per_connection: 1024,
+        max_batch_len: None,
+        max_request_body_size: 10 * 1024 * 1024,
+        max_response_body_size: 10 * 1024 * 1024,
+        call_timeout: None,
+    };
+
+    /// Returns the limits of the configuration, using the defaults for the missing ones. A batch
+    /// length or a call timeout of 0 means unlimited.
+    pub fn from_config(config: &Config) -> Self {
+        let max_batch_len = match config.rpc_max_batch_length {
+            Some(0) => None,
+            Some(max_batch_len) => Some(max_batch_len),
+            None => Self::DEFAULT.max_batch_len,
+        };
+
+        Self {
+            max_connections: config.rpc_max_connections.unwrap_or(Self::DEFAULT.max_connections),
+            max_subscriptions_per_connection: config
+                .rpc_max_subscriptions_per_connection
+                .unwrap_or(Self::DEFAULT.max_subscriptions_per_connection),
+            max_batch_len,
+            max_request_body_size: config.rpc_max_request_body_size.unwrap_or(Self::DEFAULT.max_request_body_size),
+            max_response_body_size: config.rpc_max_response_body_size.unwrap_or(Self::DEFAULT.max_response_body_size),
+            call_timeout: config
+                .rpc_call_timeout_seconds
+                .filter(|timeout| *timeout > 0)
+                .map(|timeout| Duration::from_secs(timeout.into())),
+        }
+    }
+}
+
+/// Default cost of the methods which are more expensive to serve than a single read.
+const DEFAULT_METHOD_WEIGHTS: [(&str, u32); 5] =
+    [("debug_*", 50), ("trace_*", 50), ("eth_call", 10), ("eth_estimateGas", 10), ("eth_getLogs", 10)];
+
+/// Token bucket rate limits of the RPC clients, identified by their IP address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// The tokens refilled each second in the bucket of a client, unlimited if `None`.
+    pub tokens_per_second: Option<u32>,
+    /// The capacity of the bucket of a client.
+    pub burst: u32,
+    /// The maximum calls per second of a client to a method, by method pattern.
+    pub method_limits: Vec<(String, u32)>,
+    /// The tokens consumed by a call to a method, by method pattern. Methods
+    /// without a weight consume a single token.
+    pub method_weights: Vec<(String, u32)>,
+}
+
+impl RateLimitConfig {
+    /// Returns the rate limits of the configuration. Method limits and weights are lists of
+    /// `method=value`, where methods can be matched by prefix with a trailing `*`.
+    /// Returns `None` if neither a client nor a method limit is set.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let tokens_per_second = config.rpc_rate_limit_per_second.filter(|rate| *rate > 0);
+        let method_limits = method_values("rpc_rate_limit_methods", config.rpc_rate_limit_methods.as_deref())?;
+        if tokens_per_second.is_none() && method_limits.is_empty() {
+            return Ok(None);
+        }
+
+        let burst = config.rpc_rate_limit_burst.or(tokens_per_second).unwrap_or_default();
+
+        // Configured weights take precedence over the default ones.
+        let mut method_weights = method_values("rpc_rate_limit_weights", config.rpc_rate_limit_weights.as_deref())?;
+        method_weights.extend(DEFAULT_METHOD_WEIGHTS.map(|(method, weight)| (method.to_string(), weight)));
+
+        Ok(Some(Self { tokens_per_second, burst, method_limits, method_weights }))
+    }
+
+    /// Returns the tokens consumed by a call to the method.
+    pub fn method_weight(&self, method: &str) -> u32 {
+        method_value(&self.method_weights, method).unwrap_or(1)
+    }
+
+    /// Returns the maximum calls per second of a client to the method, if limited.
+    pub fn method_limit(&self, method: &str) -> Option<u32> {
+        method_value(&self.method_limits, method)
+    }
+}
+
+/// The modules served by the admin RPC server by default.
+const DEFAULT_ADMIN_MODULES: [KakarotRpcModule; 3] =
+    [KakarotRpcModule::Debug, KakarotRpcModule::Trace, KakarotRpcModule::Txpool];
+
+/// Configuration of the admin RPC server, serving privileged methods to clients
+/// authenticated with a JWT.
+#[derive(Debug, Clone)]
+pub struct AdminRPCConfig {
+    pub socket_addr: String,
+    /// Path to the file containing the hex encoded HS256 secret, created if missing.
+    pub jwt_secret_path: PathBuf,
+    /// The modules and methods served by the admin server.
+    pub modules: RpcModulesConfig,
+}
+
+impl AdminRPCConfig {
+    /// Returns the configuration of the admin server, or `None` if `admin_rpc_url` isn't set,
+    /// in which case the admin server isn't started.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let Some(socket_addr) = config.admin_rpc_url.clone().filter(|url| !url.is_empty()) else {
+            return Ok(None);
+        };
+        let jwt_secret_path = config
+            .admin_rpc_jwt_secret_path
+            .clone()
+            .ok_or_else(|| eyre!("Missing admin_rpc_jwt_secret_path (env ADMIN_RPC_JWT_SECRET_PATH)"))?;
+        let modules = config
+            .admin_rpc_api
+            .as_ref()
+            .map_or_else(|| DEFAULT_ADMIN_MODULES.into(), |modules| modules.iter().copied().collect());
+
+        Ok(Some(Self {
+            socket_addr,
+            jwt_secret_path,
+            modules: RpcModulesConfig { modules: Some(modules), ..Default::default() },
+        }))
+    }
+}
+
+/// Parses a list of `method=value`, ignoring the empty items.
+fn method_values(name: &str, items: Option<&[String]>) -> Result<Vec<(String, u32)>> {
+    items
+        .unwrap_or_default()
+        .iter()
+        .filter(|item| !item.trim().is_empty())
+        .map(|item| {
+            let (method, value) = item.split_once('=').ok_or_else(|| eyre!("Invalid {name} entry {item}"))?;
+            let value = value.trim().parse().map_err(|err| eyre!("Invalid {name} entry {item}: {err}"))?;
+            Ok((method.trim().to_string(), value))
+        })
+        .collect()
+}
+
+/// Returns the value of the first pattern matching the method.
+fn method_value(values: &[(String, u32)], method: &str) -> Option<u32> {
+    values.iter().find(|(pattern, _)| method_matches(pattern, method)).map(|(_, value)| *value)
+}
+
+/// Returns true if the method matches the pattern, either exactly or by prefix with a trailing `*`.
+fn method_matches(pattern: &str, method: &str) -> bool {
+    pattern.strip_suffix('*').map_or(pattern == method, |prefix| method.starts_with(prefix))
 }
 
 #[cfg(feature = "testing")]
@@ -44,3 +261,146 @@ impl RPCConfig {
         config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_modules_config_method_filter() {
+        // Given
+        let config = RpcModulesConfig {
+            modules: Some(HashSet::from([KakarotRpcModule::Eth, KakarotRpcModule::Net])),
+            allowed_methods: Some(HashSet::from(["eth_*".to_string(), "net_version".to_string()])),
+            denied_methods: HashSet::from(["eth_sendRawTransaction".to_string()]),
+        };
+
+        // When
+        let modules = [KakarotRpcModule::Eth, KakarotRpcModule::Debug].map(|module| config.is_module_enabled(module));
+        let methods = ["eth_call", "eth_sendRawTransaction", "net_version", "net_listening", "debug_traceCall"]
+            .map(|method| config.is_method_allowed(method));
+
+        // Then
+        assert_eq!(modules, [true, false]);
+        assert_eq!(methods, [true, false, true, false, false]);
+    }
+
+    #[test]
+    fn test_rpc_modules_config_from_config() {
+        // Given
+        let config: Config = toml::from_str(
+            r#"
+            rpc_http_api = ["eth", "kakarot"]
+            rpc_methods_allowlist = []
+            rpc_methods_denylist = ["eth_sendRawTransaction"]
+            "#,
+        )
+        .unwrap();
+
+        // When
+        let modules_config = RpcModulesConfig::from_config(&config);
+
+        // Then
+        assert_eq!(modules_config.modules, Some(HashSet::from([KakarotRpcModule::Eth, KakarotRpcModule::KakarotRpc])));
+        assert_eq!(modules_config.allowed_methods, None);
+        assert_eq!(modules_config.denied_methods, HashSet::from(["eth_sendRawTransaction".to_string()]));
+    }
+
+    #[test]
+    fn test_rpc_modules_config_exclude_modules() {
+        // Given
+        let mut config = RpcModulesConfig::default();
+        let admin_modules = HashSet::from(DEFAULT_ADMIN_MODULES);
+
+        // When
+        config.exclude_modules(&admin_modules);
+
+        // Then
+        assert!(config.is_module_enabled(KakarotRpcModule::Eth));
+        assert!(DEFAULT_ADMIN_MODULES.iter().all(|module| !config.is_module_enabled(*module)));
+    }
+
+    #[test]
+    fn test_request_limits_config_from_config() {
+        // Given
+        let config: Config = toml::from_str(
+            r#"
+            rpc_max_connections = 10
+            rpc_max_batch_length = 50
+            rpc_call_timeout_seconds = 30
+            "#,
+        )
+        .unwrap();
+
+        // When
+        let limits = RequestLimitsConfig::from_config(&config);
+
+        // Then
+        assert_eq!(
+            limits,
+            RequestLimitsConfig {
+                max_connections: 10,
+                max_batch_len: Some(50),
+                call_timeout: Some(Duration::from_secs(30)),
+                ..RequestLimitsConfig::DEFAULT
+            }
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_config_method_values() {
+        // Given
+        let config = RateLimitConfig {
+            tokens_per_second: Some(100),
+            burst: 100,
+            method_limits: vec![("debug_traceBlockByNumber".to_string(), 1)],
+            method_weights: vec![("debug_traceCall".to_string(), 20), ("debug_*".to_string(), 50)],
+        };
+
+        // When
+        let weights =
+            ["debug_traceCall", "debug_traceBlockByNumber", "eth_chainId"].map(|method| config.method_weight(method));
+        let limits = ["debug_traceBlockByNumber", "eth_chainId"].map(|method| config.method_limit(method));
+
+        // Then
+        assert_eq!(weights, [20, 50, 1]);
+        assert_eq!(limits, [Some(1), None]);
+    }
+
+    #[test]
+    fn test_rate_limit_config_from_config() {
+        // Given
+        let config: Config = toml::from_str(
+            r#"
+            rpc_rate_limit_per_second = 100
+            rpc_rate_limit_weights = ["debug_traceCall=20"]
+            "#,
+        )
+        .unwrap();
+        let invalid_config = Config { rpc_rate_limit_methods: Some(vec!["eth_call".to_string()]), ..config.clone() };
+
+        // When
+        let rate_limit = RateLimitConfig::from_config(&config).unwrap().unwrap();
+        let invalid_rate_limit = RateLimitConfig::from_config(&invalid_config);
+
+        // Then
+        assert_eq!(rate_limit.burst, 100);
+        assert_eq!(rate_limit.method_weight("debug_traceCall"), 20);
+        assert_eq!(rate_limit.method_weight("debug_traceTransaction"), 50);
+        assert!(invalid_rate_limit.is_err());
+    }
+
+    #[test]
+    fn test_rpc_modules_config_default_serves_everything() {
+        // Given
+        let config = RpcModulesConfig::default();
+
+        // When
+        let module_enabled = config.is_module_enabled(KakarotRpcModule::Debug);
+        let method_allowed = config.is_method_allowed("debug_traceCall");
+
+        // Then
+        assert!(module_enabled);
+        assert!(method_allowed);
+    }
+}
@@ -0,0 +1,73 @@
+//! HTTP middleware authenticating the requests with a JWT, as done for the engine API.
+
+use alloy_rpc_types_engine::JwtSecret;
+use futures::future::{ready, Either, Ready};
+use hyper::{
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    Request as HttpRequest, Response as HttpResponse,
+};
+use std::task::{Context, Poll};
+
+/// HTTP layer rejecting the requests without a valid `Authorization: Bearer <JWT>` header.
+/// The JWT must be signed with the HS256 secret and issued within 60 seconds.
+#[derive(Debug, Clone)]
+pub struct JwtAuthLayer {
+    secret: JwtSecret,
+}
+
+impl JwtAuthLayer {
+    /// Create a new [`JwtAuthLayer`].
+    pub const fn new(secret: JwtSecret) -> Self {
+        Self { secret }
+    }
+}
+
+impl<S> tower::Layer<S> for JwtAuthLayer {
+    type Service = JwtAuth<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        JwtAuth { service, secret: self.secret }
+    }
+}
+
+/// HTTP service authenticating the requests with a JWT.
+#[derive(Debug, Clone)]
+pub struct JwtAuth<S> {
+    service: S,
+    secret: JwtSecret,
+}
+
+impl<S, B, ResBody> tower::Service<HttpRequest<B>> for JwtAuth<S>
+where
+    S: tower::Service<HttpRequest<B>, Response = HttpResponse<ResBody>>,
+    ResBody: From<String>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Self::Response, Self::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest<B>) -> Self::Future {
+        match authenticate(&self.secret, request.headers()) {
+            Ok(()) => Either::Right(self.service.call(request)),
+            Err(message) => {
+                let mut response = HttpResponse::new(ResBody::from(message));
+                *response.status_mut() = StatusCode::UNAUTHORIZED;
+                Either::Left(ready(Ok(response)))
+            }
+        }
+    }
+}
+
+/// Validates the JWT of the bearer `Authorization` header.
+fn authenticate(secret: &JwtSecret, headers: &HeaderMap) -> Result<(), String> {
+    let jwt = headers
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "))
+        .ok_or_else(|| "missing bearer JWT".to_string())?;
+    secret.validate(jwt).map_err(|err| err.to_string())
+}
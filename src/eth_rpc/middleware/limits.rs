@@ -0,0 +1,296 @@
+//! Middlewares enforcing the size limits of the requests and responses and the timeout of the calls.
+//!
+//! The server enforces the same limits, the middlewares return the same errors earlier and record the
+//! rejections in prometheus metrics.
+
+use crate::{
+    eth_rpc::config::RequestLimitsConfig,
+    prometheus_handler::{register, CounterVec, Opts, PrometheusError, Registry, U64},
+    providers::eth_provider::error::EthRpcErrorCode,
+};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::{
+    body::{Body as HttpBody, Bytes},
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    Request as HttpRequest, Response as HttpResponse,
+};
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{
+        error::{
+            OVERSIZED_REQUEST_CODE, OVERSIZED_REQUEST_MSG, OVERSIZED_RESPONSE_CODE, PARSE_ERROR_CODE, PARSE_ERROR_MSG,
+            TOO_BIG_BATCH_REQUEST_CODE, TOO_BIG_BATCH_REQUEST_MSG,
+        },
+        ErrorObject, Id, Request,
+    },
+    MethodResponse,
+};
+use pin_project_lite::pin_project;
+use serde::de::IgnoredAny;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Timeout;
+
+/// The reason of the rejection of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rejection {
+    BatchTooLarge,
+    RequestTooLarge,
+    ResponseTooLarge,
+    Timeout,
+}
+
+impl Rejection {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::BatchTooLarge => "batch_too_large",
+            Self::RequestTooLarge => "request_too_large",
+            Self::ResponseTooLarge => "response_too_large",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+/// Metrics of the requests rejected because they exceeded a limit.
+#[derive(Debug, Clone)]
+pub struct LimitsMetrics {
+    /// Number of rejected requests, by reason.
+    rejections: CounterVec<U64>,
+}
+
+impl LimitsMetrics {
+    /// Create an instance of metrics
+    pub fn new(metrics_registry: Option<&Registry>) -> Result<Option<Self>, PrometheusError> {
+        if let Some(metrics_registry) = metrics_registry {
+            Ok(Some(Self {
+                rejections: register(
+                    CounterVec::new(
+                        Opts::new("eth_rpc_limit_rejections", "Number of RPC requests exceeding a limit"),
+                        &["reason"],
+                    )?,
+                    metrics_registry,
+                )?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn record(metrics: Option<&Self>, rejection: Rejection) {
+        tracing::debug!(target: "rpc_limits", reason = rejection.label(), "request rejected");
+        if let Some(metrics) = metrics {
+            metrics.rejections.with_label_values(&[rejection.label()]).inc();
+        }
+    }
+}
+
+/// HTTP layer rejecting the requests which body or batch length exceed the limits.
+#[derive(Debug, Clone)]
+pub struct RequestLimitsLayer {
+    max_request_body_size: u32,
+    max_batch_len: Option<u32>,
+    metrics: Option<LimitsMetrics>,
+}
+
+impl RequestLimitsLayer {
+    /// Create a new [`RequestLimitsLayer`].
+    pub const fn new(config: &RequestLimitsConfig, metrics: Option<LimitsMetrics>) -> Self {
+        Self { max_request_body_size: config.max_request_body_size, max_batch_len: config.max_batch_len, metrics }
+    }
+}
+
+impl<S> tower::Layer<S> for RequestLimitsLayer {
+    type Service = RequestLimits<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RequestLimits { service, layer: self.clone() }
+    }
+}
+
+/// HTTP service buffering the body of the requests to check their size and batch length.
+#[derive(Debug, Clone)]
+pub struct RequestLimits<S> {
+    service: S,
+    layer: RequestLimitsLayer,
+}
+
+impl<S, B, ResBody> tower::Service<HttpRequest<B>> for RequestLimits<S>
+where
+    S: tower::Service<HttpRequest<Full<Bytes>>, Response = HttpResponse<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    ResBody: From<String>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest<B>) -> Self::Future {
+        // The ready service is the one which must be called.
+        let clone = self.service.clone();
+        let mut service = std::mem::replace(&mut self.service, clone);
+        let RequestLimitsLayer { max_request_body_size, max_batch_len, metrics } = self.layer.clone();
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = match Limited::new(body, max_request_body_size as usize).collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(err) if err.is::<LengthLimitError>() => {
+                    LimitsMetrics::record(metrics.as_ref(), Rejection::RequestTooLarge);
+                    let error = ErrorObject::borrowed(OVERSIZED_REQUEST_CODE, OVERSIZED_REQUEST_MSG, None);
+                    return Ok(error_response(StatusCode::PAYLOAD_TOO_LARGE, &error));
+                }
+                Err(_) => {
+                    let error = ErrorObject::borrowed(PARSE_ERROR_CODE, PARSE_ERROR_MSG, None);
+                    return Ok(error_response(StatusCode::BAD_REQUEST, &error));
+                }
+            };
+
+            if let Some(max_batch_len) = max_batch_len {
+                if batch_len(&body).is_some_and(|len| len > max_batch_len as usize) {
+                    LimitsMetrics::record(metrics.as_ref(), Rejection::BatchTooLarge);
+                    let message = Some(format!("Exceeded max limit of {max_batch_len}"));
+                    let error = ErrorObject::owned(TOO_BIG_BATCH_REQUEST_CODE, TOO_BIG_BATCH_REQUEST_MSG, message);
+                    return Ok(error_response(StatusCode::OK, &error));
+                }
+            }
+
+            service.call(HttpRequest::from_parts(parts, Full::new(body))).await
+        })
+    }
+}
+
+/// Returns the number of calls of the request if it's a batch.
+fn batch_len(body: &[u8]) -> Option<usize> {
+    if body.iter().find(|byte| !byte.is_ascii_whitespace()) != Some(&b'[') {
+        return None;
+    }
+    serde_json::from_slice::<Vec<IgnoredAny>>(body).ok().map(|batch| batch.len())
+}
+
+/// Returns a JSON-RPC error response without id, as returned by the server when it can't read the request.
+fn error_response<ResBody: From<String>>(status: StatusCode, error: &ErrorObject<'_>) -> HttpResponse<ResBody> {
+    let body = serde_json::json!({ "jsonrpc": "2.0", "error": error, "id": Id::Null }).to_string();
+    let mut response = HttpResponse::new(ResBody::from(body));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// RPC layer enforcing the timeout of the calls and recording the responses exceeding the size limit.
+#[derive(Debug, Clone)]
+pub struct CallLimitsLayer {
+    timeout: Option<Duration>,
+    metrics: Option<LimitsMetrics>,
+}
+
+impl CallLimitsLayer {
+    /// Create a new [`CallLimitsLayer`].
+    pub const fn new(config: &RequestLimitsConfig, metrics: Option<LimitsMetrics>) -> Self {
+        Self { timeout: config.call_timeout, metrics }
+    }
+}
+
+impl<S> tower::Layer<S> for CallLimitsLayer {
+    type Service = CallLimits<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        CallLimits { service, timeout: self.timeout, metrics: self.metrics.clone() }
+    }
+}
+
+/// RPC middleware enforcing the timeout of the calls.
+#[derive(Debug, Clone)]
+pub struct CallLimits<S> {
+    service: S,
+    timeout: Option<Duration>,
+    metrics: Option<LimitsMetrics>,
+}
+
+impl<'a, S> RpcServiceT<'a> for CallLimits<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<'a, S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let id = req.id();
+        ResponseFuture {
+            // Calls without timeout never time out in practice.
+            fut: tokio::time::timeout(self.timeout.unwrap_or(Duration::MAX), self.service.call(req)),
+            id,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future of a call with a timeout.
+    pub struct ResponseFuture<'a, F> {
+        #[pin]
+        fut: Timeout<F>,
+        id: Id<'a>,
+        metrics: Option<LimitsMetrics>,
+    }
+}
+
+impl<'a, F> std::fmt::Debug for ResponseFuture<'a, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ResponseFuture")
+    }
+}
+
+impl<'a, F: Future<Output = MethodResponse>> Future for ResponseFuture<'a, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        match this.fut.poll(cx) {
+            Poll::Ready(Ok(rp)) => {
+                if rp.as_error_code() == Some(OVERSIZED_RESPONSE_CODE) {
+                    LimitsMetrics::record(this.metrics.as_ref(), Rejection::ResponseTooLarge);
+                }
+                Poll::Ready(rp)
+            }
+            Poll::Ready(Err(_)) => {
+                LimitsMetrics::record(this.metrics.as_ref(), Rejection::Timeout);
+                let error =
+                    ErrorObject::borrowed(EthRpcErrorCode::ResourceUnavailable as i32, "request timed out", None);
+                Poll::Ready(MethodResponse::error(this.id.clone(), error))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_len() {
+        // Given
+        let bodies = [
+            r#"[{"jsonrpc":"2.0","method":"eth_chainId","id":1},{"jsonrpc":"2.0","method":"eth_chainId","id":2}]"#,
+            "  [ ]",
+            r#"{"jsonrpc":"2.0","method":"eth_chainId","id":1}"#,
+            r#"[{"jsonrpc":"2.0""#,
+        ];
+
+        // When
+        let lens = bodies.map(|body| batch_len(body.as_bytes()));
+
+        // Then
+        assert_eq!(lens, [Some(2), Some(0), None, None]);
+    }
+}
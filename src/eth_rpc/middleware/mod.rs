@@ -18,7 +18,12 @@
 
 //! JSON-RPC specific middleware.
 
+/// JWT authentication middleware.
+pub mod auth;
+/// Request limits middleware.
+pub mod limits;
 /// Grafana metrics middleware.
 pub mod metrics;
 /// Rate limit middleware.
+pub mod rate_limit;
 pub use metrics::*;
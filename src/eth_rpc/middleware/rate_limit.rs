@@ -0,0 +1,424 @@
+//! RPC middleware limiting the rate of the calls of each client with token buckets.
+
+use crate::{
+    eth_rpc::config::RateLimitConfig,
+    prometheus_handler::{register, CounterVec, Opts, PrometheusError, Registry, U64},
+    providers::eth_provider::error::EthRpcErrorCode,
+};
+use hyper::{http::HeaderMap, Request as HttpRequest};
+use jsonrpsee::{
+    server::middleware::rpc::{ResponseFuture, RpcServiceT},
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use schnellru::{ByLength, LruMap};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Instant,
+};
+
+/// Maximum number of clients, and of (client, method) pairs, which buckets are tracked. The least
+/// recently used buckets are dropped above this limit.
+const MAX_TRACKED_CLIENTS: u32 = 10_000;
+
+/// IP address of the client, as forwarded by the trusted proxies in front of the RPC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientIp(pub IpAddr);
+
+/// HTTP layer inserting the [`ClientIp`] of a connection in the extensions of its requests.
+/// The IP address forwarded in the `X-Forwarded-For` or `X-Real-IP` headers is only used if
+/// the peer of the connection is a trusted proxy, otherwise the peer address is used.
+#[derive(Debug, Clone)]
+pub struct ClientIpLayer {
+    peer_addr: SocketAddr,
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl ClientIpLayer {
+    /// Create a new [`ClientIpLayer`] for a connection from the peer address.
+    pub const fn new(peer_addr: SocketAddr, trusted_proxies: Arc<[IpAddr]>) -> Self {
+        Self { peer_addr, trusted_proxies }
+    }
+}
+
+impl<S> tower::Layer<S> for ClientIpLayer {
+    type Service = ClientIpService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        ClientIpService { service, peer_addr: self.peer_addr, trusted_proxies: self.trusted_proxies.clone() }
+    }
+}
+
+/// HTTP service inserting the [`ClientIp`] in the extensions of the request.
+#[derive(Debug, Clone)]
+pub struct ClientIpService<S> {
+    service: S,
+    peer_addr: SocketAddr,
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl<S, B> tower::Service<HttpRequest<B>> for ClientIpService<S>
+where
+    S: tower::Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: HttpRequest<B>) -> Self::Future {
+        let ip = client_ip(self.peer_addr.ip(), request.headers(), &self.trusted_proxies);
+        request.extensions_mut().insert(ClientIp(ip));
+        self.service.call(request)
+    }
+}
+
+/// Returns the IP address of the client. Each proxy appends the address of its peer to
+/// `X-Forwarded-For`, so the client is the last address not belonging to a trusted proxy.
+/// `X-Real-IP` is used if `X-Forwarded-For` is missing.
+fn client_ip(peer_ip: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> IpAddr {
+    if !trusted_proxies.contains(&peer_ip) {
+        return peer_ip;
+    }
+
+    let forwarded_for: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+    let mut forwarded_ip = None;
+    for ip in forwarded_for.into_iter().rev() {
+        match ip.trim().parse() {
+            Ok(ip) if trusted_proxies.contains(&ip) => forwarded_ip = Some(ip),
+            Ok(ip) => return ip,
+            Err(_) => break,
+        }
+    }
+
+    let real_ip = || headers.get("x-real-ip").and_then(|value| value.to_str().ok()?.trim().parse().ok());
+    forwarded_ip.or_else(real_ip).unwrap_or(peer_ip)
+}
+
+/// Identifier of a client. All the calls without a [`ClientIp`] share the same limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ClientKey {
+    Ip(IpAddr),
+    Unknown,
+}
+
+/// The limit which rejected a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RateLimit {
+    /// The tokens of the client are exhausted.
+    Client,
+    /// The calls per second of the client to the method are exhausted.
+    Method,
+}
+
+impl RateLimit {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Method => "method",
+        }
+    }
+}
+
+/// A token bucket, refilled continuously up to its capacity.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    rate: f64,
+    capacity: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(rate: u32, capacity: u32, now: Instant) -> Self {
+        let capacity = f64::from(capacity.max(1));
+        Self { tokens: capacity, rate: f64::from(rate), capacity, updated_at: now }
+    }
+
+    /// Refills the bucket with the tokens accumulated since the last update.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = elapsed.mul_add(self.rate, self.tokens).min(self.capacity);
+        self.updated_at = now;
+    }
+
+    /// Returns true if the bucket holds the tokens of the call. A call can't cost
+    /// more than the capacity, otherwise it would never be allowed.
+    fn has_tokens(&mut self, cost: f64, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= cost.min(self.capacity)
+    }
+
+    fn consume(&mut self, cost: f64) {
+        self.tokens -= cost.min(self.capacity);
+    }
+}
+
+/// Buckets of the clients and of the (client, method) pairs, the least recently used
+/// ones being dropped above [`MAX_TRACKED_CLIENTS`].
+#[derive(Debug)]
+struct Buckets {
+    clients: LruMap<ClientKey, TokenBucket>,
+    methods: LruMap<(ClientKey, String), TokenBucket>,
+}
+
+impl Default for Buckets {
+    fn default() -> Self {
+        Self {
+            clients: LruMap::new(ByLength::new(MAX_TRACKED_CLIENTS)),
+            methods: LruMap::new(ByLength::new(MAX_TRACKED_CLIENTS)),
+        }
+    }
+}
+
+/// Token bucket rate limiter of the RPC clients.
+#[derive(Debug)]
+struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    fn new(config: RateLimitConfig) -> Self {
+        Self { config, buckets: Mutex::default() }
+    }
+
+    /// Consumes the tokens of a call of the client to the method, or returns the exhausted limit.
+    /// Tokens are only consumed if the call is allowed by all the limits.
+    fn check(&self, client: ClientKey, method: &str, now: Instant) -> Result<(), RateLimit> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Buckets { clients, methods } = &mut *buckets;
+
+        let mut client_bucket = None;
+        if let Some(rate) = self.config.tokens_per_second {
+            let cost = f64::from(self.config.method_weight(method));
+
+            // the map tracks at least one bucket, so the insertion can't fail.
+            let bucket = clients
+                .get_or_insert(client, || TokenBucket::full(rate, self.config.burst, now))
+                .expect("bucket inserted");
+            if !bucket.has_tokens(cost, now) {
+                return Err(RateLimit::Client);
+            }
+            client_bucket = Some((bucket, cost));
+        }
+
+        if let Some(rate) = self.config.method_limit(method) {
+            let bucket = methods
+                .get_or_insert((client, method.to_string()), || TokenBucket::full(rate, rate, now))
+                .expect("bucket inserted");
+            if !bucket.has_tokens(1., now) {
+                return Err(RateLimit::Method);
+            }
+            bucket.consume(1.);
+        }
+
+        if let Some((bucket, cost)) = client_bucket {
+            bucket.consume(cost);
+        }
+        Ok(())
+    }
+}
+
+/// Metrics of the calls rejected by the rate limiter.
+#[derive(Debug, Clone)]
+pub struct RateLimitMetrics {
+    /// Number of calls rejected.
+    rejections: CounterVec<U64>,
+}
+
+impl RateLimitMetrics {
+    /// Create an instance of metrics
+    pub fn new(metrics_registry: Option<&Registry>) -> Result<Option<Self>, PrometheusError> {
+        if let Some(metrics_registry) = metrics_registry {
+            Ok(Some(Self {
+                rejections: register(
+                    CounterVec::new(
+                        Opts::new("eth_rpc_rate_limit_rejections", "Number of RPC calls rejected by the rate limiter"),
+                        &["method", "limit"],
+                    )?,
+                    metrics_registry,
+                )?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Rate limit layer.
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+    metrics: Option<RateLimitMetrics>,
+}
+
+impl RateLimitLayer {
+    /// Create a new [`RateLimitLayer`].
+    pub fn new(config: RateLimitConfig, metrics: Option<RateLimitMetrics>) -> Self {
+        Self { limiter: Arc::new(RateLimiter::new(config)), metrics }
+    }
+}
+
+impl<S> tower::Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimitService { service, limiter: self.limiter.clone(), metrics: self.metrics.clone() }
+    }
+}
+
+/// Rate limit middleware.
+#[derive(Clone, Debug)]
+pub struct RateLimitService<S> {
+    service: S,
+    limiter: Arc<RateLimiter>,
+    metrics: Option<RateLimitMetrics>,
+}
+
+impl<'a, S> RpcServiceT<'a> for RateLimitService<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let client = req.extensions().get::<ClientIp>().map_or(ClientKey::Unknown, |ip| ClientKey::Ip(ip.0));
+
+        match self.limiter.check(client, req.method_name(), Instant::now()) {
+            Ok(()) => ResponseFuture::future(self.service.call(req)),
+            Err(limit) => {
+                tracing::debug!(target: "rpc_rate_limit", ?client, method = req.method_name(), ?limit, "call rejected");
+                if let Some(metrics) = &self.metrics {
+                    metrics.rejections.with_label_values(&[req.method_name(), limit.label()]).inc();
+                }
+                ResponseFuture::ready(MethodResponse::error(
+                    req.id(),
+                    ErrorObject::borrowed(EthRpcErrorCode::RequestLimitExceeded as i32, "limit exceeded", None),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config(tokens_per_second: Option<u32>, burst: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            tokens_per_second,
+            burst,
+            method_limits: vec![("eth_call".to_string(), 2)],
+            method_weights: vec![("debug_*".to_string(), 5)],
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter_weighted_client_limit() {
+        // Given
+        let limiter = RateLimiter::new(config(Some(10), 10));
+        let client = ClientKey::Ip([127, 0, 0, 1].into());
+        let other_client = ClientKey::Ip([127, 0, 0, 2].into());
+        let now = Instant::now();
+
+        // When
+        let first_trace = limiter.check(client, "debug_traceTransaction", now);
+        let second_trace = limiter.check(client, "debug_traceTransaction", now);
+        let third_trace = limiter.check(client, "debug_traceTransaction", now);
+        let chain_id = limiter.check(client, "eth_chainId", now);
+        let other_client_trace = limiter.check(other_client, "debug_traceTransaction", now);
+        let refilled_trace = limiter.check(client, "debug_traceTransaction", now + Duration::from_millis(500));
+
+        // Then
+        assert_eq!(first_trace, Ok(()));
+        assert_eq!(second_trace, Ok(()));
+        assert_eq!(third_trace, Err(RateLimit::Client));
+        assert_eq!(chain_id, Err(RateLimit::Client));
+        assert_eq!(other_client_trace, Ok(()));
+        assert_eq!(refilled_trace, Ok(()));
+    }
+
+    #[test]
+    fn test_rate_limiter_method_limit() {
+        // Given
+        let limiter = RateLimiter::new(config(None, 0));
+        let client = ClientKey::Ip([127, 0, 0, 1].into());
+        let now = Instant::now();
+
+        // When
+        let calls: Vec<_> = (0..3).map(|_| limiter.check(client, "eth_call", now)).collect();
+        let chain_id = limiter.check(client, "eth_chainId", now);
+        let refilled_call = limiter.check(client, "eth_call", now + Duration::from_secs(1));
+
+        // Then
+        assert_eq!(calls, vec![Ok(()), Ok(()), Err(RateLimit::Method)]);
+        assert_eq!(chain_id, Ok(()));
+        assert_eq!(refilled_call, Ok(()));
+    }
+
+    #[test]
+    fn test_rate_limiter_rejected_call_consumes_no_token() {
+        // Given
+        let limiter = RateLimiter::new(config(Some(3), 3));
+        let client = ClientKey::Unknown;
+        let now = Instant::now();
+
+        // When
+        let calls: Vec<_> = (0..3).map(|_| limiter.check(client, "eth_call", now)).collect();
+        let chain_id = limiter.check(client, "eth_chainId", now);
+
+        // Then
+        assert_eq!(calls, vec![Ok(()), Ok(()), Err(RateLimit::Method)]);
+        assert_eq!(chain_id, Ok(()));
+    }
+
+    #[test]
+    fn test_rate_limiter_tracks_a_bounded_number_of_clients() {
+        // Given
+        let limiter = RateLimiter::new(config(Some(1), 1));
+        let now = Instant::now();
+
+        // When
+        for i in 0..=MAX_TRACKED_CLIENTS {
+            let _ = limiter.check(ClientKey::Ip(IpAddr::from(i.to_be_bytes())), "eth_chainId", now);
+        }
+
+        // Then
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.clients.len(), MAX_TRACKED_CLIENTS as usize);
+        assert!(buckets.clients.peek(&ClientKey::Ip(IpAddr::from(0u32.to_be_bytes()))).is_none());
+    }
+
+    #[test]
+    fn test_client_ip() {
+        // Given
+        let proxy: IpAddr = [10, 0, 0, 1].into();
+        let peer: IpAddr = [203, 0, 113, 9].into();
+        let trusted_proxies = [proxy];
+        let mut forwarded_for = HeaderMap::new();
+        forwarded_for.insert("x-forwarded-for", "198.51.100.1, 203.0.113.1, 10.0.0.1".parse().unwrap());
+        let mut real_ip = HeaderMap::new();
+        real_ip.insert("x-real-ip", "203.0.113.2".parse().unwrap());
+
+        // When
+        let from_proxy =
+            [&forwarded_for, &real_ip, &HeaderMap::new()].map(|headers| client_ip(proxy, headers, &trusted_proxies));
+        let from_peer = client_ip(peer, &forwarded_for, &trusted_proxies);
+
+        // Then
+        assert_eq!(from_proxy, [[203, 0, 113, 1].into(), [203, 0, 113, 2].into(), proxy]);
+        assert_eq!(from_peer, peer);
+    }
+}
@@ -8,21 +8,36 @@ pub mod rpc;
 pub mod servers;
 
 use crate::{
-    eth_rpc::middleware::{metrics::RpcMetrics, MetricsLayer},
-    prometheus_handler::init_prometheus,
+    eth_rpc::middleware::{
+        auth::JwtAuthLayer,
+        limits::{CallLimitsLayer, LimitsMetrics, RequestLimitsLayer},
+        metrics::RpcMetrics,
+        rate_limit::{ClientIpLayer, RateLimitLayer, RateLimitMetrics},
+        MetricsLayer,
+    },
+    prometheus_handler::Registry,
 };
-use config::RPCConfig;
+use alloy_primitives::hex;
+use alloy_rpc_types_engine::{JwtError, JwtSecret};
+use config::{AdminRPCConfig, RPCConfig, RequestLimitsConfig};
 use eyre::Result;
 use jsonrpsee::{
     server::{
         middleware::http::{InvalidPath, ProxyGetRequestLayer},
-        RpcServiceBuilder, ServerBuilder, ServerHandle,
+        serve_with_graceful_shutdown, stop_channel, BatchRequestConfig, RpcServiceBuilder, ServerBuilder, ServerHandle,
     },
-    RpcModule,
+    Methods, RpcModule,
+};
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    net::{AddrParseError, IpAddr, SocketAddr},
+    path::Path,
+    sync::Arc,
 };
-use prometheus::Registry;
-use std::net::{AddrParseError, Ipv4Addr, SocketAddr};
 use thiserror::Error;
+use tokio::net::TcpListener;
+use tower::Layer;
 use tower_http::cors::{Any, CorsLayer};
 
 #[derive(Error, Debug)]
@@ -37,55 +52,172 @@ pub enum RpcError {
     PrometheusHandlerError(#[from] crate::prometheus_handler::Error),
     #[error(transparent)]
     PrometheusError(#[from] prometheus::Error),
+    #[error(transparent)]
+    JwtError(#[from] JwtError),
+}
+
+/// Prometheus metrics of the RPC servers, shared by the RPC and admin RPC servers.
+#[derive(Debug, Clone)]
+pub struct RpcServerMetrics {
+    /// Calls and timings of the RPC calls, labelled by server.
+    rpc: Option<RpcMetrics>,
+    /// Calls rejected by the rate limiter.
+    rate_limit: Option<RateLimitMetrics>,
+    /// Requests exceeding a limit.
+    limits: Option<LimitsMetrics>,
 }
 
+impl RpcServerMetrics {
+    /// Registers the metrics in the given registry, which should then be served to prometheus.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the metrics are already registered.
+    pub fn new(registry: &Registry) -> Result<Self, RpcError> {
+        Ok(Self {
+            rpc: RpcMetrics::new(Some(registry))?,
+            rate_limit: RateLimitMetrics::new(Some(registry))?,
+            limits: LimitsMetrics::new(Some(registry))?,
+        })
+    }
+}
+
+/// Starts the RPC server, serving both HTTP and WebSocket connections on the same socket.
+/// Subscriptions (`eth_subscribe`) are only available over WebSocket. The calls are recorded in
+/// the given metrics, labelled with the `http` protocol.
+///
+/// The connections are accepted by the server rather than by jsonrpsee, so that the clients can be
+/// identified by the address of their peer, or by the address forwarded by a trusted proxy.
+///
 /// # Errors
 ///
-/// Will return `Err` if an error occurs when running the `ServerBuilder` start fails.
+/// Will return `Err` if the socket address is invalid or can't be bound.
 pub async fn run_server(
     kakarot_rpc_module: RpcModule<()>,
     rpc_config: RPCConfig,
+    metrics: RpcServerMetrics,
 ) -> Result<(SocketAddr, ServerHandle), RpcError> {
-    let RPCConfig { socket_addr } = rpc_config;
+    let RPCConfig { socket_addr, rate_limit, trusted_proxies, limits } = rpc_config;
 
     let cors = CorsLayer::new().allow_methods(Any).allow_origin(Any).allow_headers(Any);
 
-    let http_middleware =
-        tower::ServiceBuilder::new().layer(ProxyGetRequestLayer::new("/health", "net_health")?).layer(cors);
+    let http_middleware = tower::ServiceBuilder::new()
+        .layer(ProxyGetRequestLayer::new("/health", "net_health")?)
+        .layer(cors)
+        .layer(RequestLimitsLayer::new(&limits, metrics.limits.clone()));
 
-    // Creating the prometheus registry to register the metrics
-    let registry = Registry::new();
-    // register the metrics
-    let metrics = RpcMetrics::new(Some(&registry))?.map(|m| MetricsLayer::new(m, "http"));
-    tokio::spawn(async move {
-        // serve the prometheus metrics on the given port so that it can be read
-        let _ = init_prometheus(
-            SocketAddr::new(
-                std::net::IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
-                get_env_or_default("PROMETHEUS_PORT", "9615").parse().unwrap(),
-            ),
-            registry,
-        )
-        .await;
-    });
     // add the metrics as a middleware to the RPC so that every new RPC call fires prometheus metrics
     // upon start, finish etc. we don't need to manually handle each method, it should automatically
     // work for any new method.
-    let rpc_middleware = RpcServiceBuilder::new().option_layer(metrics);
+    let rpc_middleware = RpcServiceBuilder::new().option_layer(metrics.rpc.map(|m| MetricsLayer::new(m, "http")));
+    // rate limit the calls of each client, after the metrics so that rejected calls are recorded as errors.
+    let rpc_middleware = rpc_middleware
+        .option_layer(rate_limit.map(|config| RateLimitLayer::new(config, metrics.rate_limit)))
+        .layer(CallLimitsLayer::new(&limits, metrics.limits));
+
+    let batch_config = limits.max_batch_len.map_or(BatchRequestConfig::Unlimited, BatchRequestConfig::Limit);
+
+    let service_builder = ServerBuilder::default()
+        .max_connections(limits.max_connections)
+        .max_subscriptions_per_connection(limits.max_subscriptions_per_connection)
+        .set_batch_request_config(batch_config)
+        .max_request_body_size(limits.max_request_body_size)
+        .max_response_body_size(limits.max_response_body_size)
+        .set_http_middleware(http_middleware)
+        .set_rpc_middleware(rpc_middleware)
+        .to_service_builder();
+
+    let listener = TcpListener::bind(socket_addr.parse::<SocketAddr>()?).await?;
+    let addr = listener.local_addr()?;
+
+    let methods: Methods = kakarot_rpc_module.into();
+    let trusted_proxies: Arc<[IpAddr]> = trusted_proxies.into();
+    let (stop_handle, handle) = stop_channel();
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, peer_addr) = tokio::select! {
+                connection = listener.accept() => match connection {
+                    Ok(connection) => connection,
+                    Err(err) => {
+                        tracing::debug!(%err, "failed to accept connection");
+                        continue;
+                    }
+                },
+                () = stop_handle.clone().shutdown() => break,
+            };
+
+            let service = ClientIpLayer::new(peer_addr, trusted_proxies.clone())
+                .layer(service_builder.clone().build(methods.clone(), stop_handle.clone()));
+            tokio::spawn(serve_with_graceful_shutdown(socket, service, stop_handle.clone().shutdown()));
+        }
+    });
+
+    Ok((addr, handle))
+}
+
+/// Starts the admin RPC server, serving both HTTP and WebSocket connections on the same socket.
+/// Requests must be authenticated with a JWT signed with the HS256 secret of the configuration,
+/// as done for the engine API. The requests are subject to the same limits as the RPC server, and
+/// the calls are recorded in the given metrics, labelled with the `admin` protocol.
+///
+/// # Errors
+///
+/// Will return `Err` if the JWT secret can't be loaded or if the `ServerBuilder` start fails.
+pub async fn run_admin_server(
+    admin_rpc_module: RpcModule<()>,
+    admin_rpc_config: AdminRPCConfig,
+    limits: RequestLimitsConfig,
+    metrics: RpcServerMetrics,
+) -> Result<(SocketAddr, ServerHandle), RpcError> {
+    let AdminRPCConfig { socket_addr, jwt_secret_path, .. } = admin_rpc_config;
+
+    let secret = load_or_create_jwt_secret(&jwt_secret_path)?;
+    // authenticate the requests before reading their body.
+    let http_middleware = tower::ServiceBuilder::new()
+        .layer(JwtAuthLayer::new(secret))
+        .layer(RequestLimitsLayer::new(&limits, metrics.limits.clone()));
+    let rpc_middleware = RpcServiceBuilder::new()
+        .option_layer(metrics.rpc.map(|m| MetricsLayer::new(m, "admin")))
+        .layer(CallLimitsLayer::new(&limits, metrics.limits));
+
+    let batch_config = limits.max_batch_len.map_or(BatchRequestConfig::Unlimited, BatchRequestConfig::Limit);
 
     let server = ServerBuilder::default()
-        .max_connections(get_env_or_default("RPC_MAX_CONNECTIONS", "100").parse().unwrap())
+        .max_connections(limits.max_connections)
+        .max_subscriptions_per_connection(limits.max_subscriptions_per_connection)
+        .set_batch_request_config(batch_config)
+        .max_request_body_size(limits.max_request_body_size)
+        .max_response_body_size(limits.max_response_body_size)
         .set_http_middleware(http_middleware)
         .set_rpc_middleware(rpc_middleware)
         .build(socket_addr.parse::<SocketAddr>()?)
         .await?;
 
     let addr = server.local_addr()?;
-    let handle = server.start(kakarot_rpc_module);
+    let handle = server.start(admin_rpc_module);
 
     Ok((addr, handle))
 }
 
-fn get_env_or_default(name: &str, default: &str) -> String {
-    std::env::var(name).unwrap_or_else(|_| default.to_string())
+/// Loads the hex encoded JWT secret from the file, or creates a random one if the file doesn't exist.
+/// The created file is only readable and writable by its owner.
+fn load_or_create_jwt_secret(path: &Path) -> Result<JwtSecret, RpcError> {
+    if path.exists() {
+        return Ok(JwtSecret::from_file(path)?);
+    }
+
+    tracing::info!(path = %path.display(), "Creating a new JWT secret for the admin RPC server");
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let secret = JwtSecret::random();
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(hex::encode(secret.as_bytes()).as_bytes())?;
+
+    Ok(secret)
 }
@@ -3,24 +3,29 @@ use crate::{
     eth_rpc::{
         api::{
             alchemy_api::AlchemyApiServer, debug_api::DebugApiServer, eth_api::EthApiServer,
-            kakarot_api::KakarotApiServer, net_api::NetApiServer, trace_api::TraceApiServer,
-            txpool_api::TxPoolApiServer, web3_api::Web3ApiServer,
+            eth_pubsub_api::EthPubSubApiServer, kakarot_api::KakarotApiServer, net_api::NetApiServer,
+            trace_api::TraceApiServer, txpool_api::TxPoolApiServer, web3_api::Web3ApiServer,
         },
+        config::RpcModulesConfig,
         servers::{
-            alchemy_rpc::AlchemyRpc, debug_rpc::DebugRpc, eth_rpc::EthRpc, kakarot_rpc::KakarotRpc, net_rpc::NetRpc,
-            trace_rpc::TraceRpc, txpool_rpc::TxpoolRpc, web3_rpc::Web3Rpc,
+            alchemy_rpc::AlchemyRpc, debug_rpc::DebugRpc, eth_pubsub_rpc::EthPubSubRpc, eth_rpc::EthRpc,
+            kakarot_rpc::KakarotRpc, net_rpc::NetRpc, trace_rpc::TraceRpc, txpool_rpc::TxpoolRpc, web3_rpc::Web3Rpc,
         },
     },
     providers::{
-        alchemy_provider::AlchemyDataProvider, debug_provider::DebugDataProvider, pool_provider::PoolDataProvider,
+        alchemy_provider::AlchemyDataProvider, debug_provider::DebugDataProvider, filter_provider::FilterDataProvider,
+        kakarot_provider::KakarotDataProvider, pool_provider::PoolDataProvider,
     },
 };
+use eyre::eyre;
 use jsonrpsee::{server::RegisterMethodError, Methods, RpcModule};
+use serde::{Deserialize, Serialize};
 use starknet::providers::Provider;
-use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, str::FromStr, sync::Arc};
 
 /// Represents RPC modules that are supported by reth
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum KakarotRpcModule {
     Eth,
     Alchemy,
@@ -29,12 +34,39 @@ pub enum KakarotRpcModule {
     Debug,
     Trace,
     Txpool,
+    #[serde(rename = "kakarot")]
     KakarotRpc,
 }
 
+impl KakarotRpcModule {
+    /// All the modules.
+    pub const ALL: [Self; 8] =
+        [Self::Eth, Self::Alchemy, Self::Web3, Self::Net, Self::Debug, Self::Trace, Self::Txpool, Self::KakarotRpc];
+}
+
+impl FromStr for KakarotRpcModule {
+    type Err = eyre::Report;
+
+    /// Parses the module from its namespace, e.g. `eth`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "eth" => Ok(Self::Eth),
+            "alchemy" => Ok(Self::Alchemy),
+            "web3" => Ok(Self::Web3),
+            "net" => Ok(Self::Net),
+            "debug" => Ok(Self::Debug),
+            "trace" => Ok(Self::Trace),
+            "txpool" => Ok(Self::Txpool),
+            "kakarot" => Ok(Self::KakarotRpc),
+            _ => Err(eyre!("unknown RPC module {s}")),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct KakarotRpcModuleBuilder<SP> {
     modules: HashMap<KakarotRpcModule, Methods>,
+    config: RpcModulesConfig,
     _phantom: PhantomData<SP>,
 }
 
@@ -42,20 +74,28 @@ impl<SP> KakarotRpcModuleBuilder<SP>
 where
     SP: Provider + Clone + Send + Sync + 'static,
 {
+    /// # Panics
+    ///
+    /// Panics if the methods of the Ethereum publish-subscribe module collide with the Ethereum module.
     pub fn new(eth_client: Arc<EthClient<SP>>) -> Self {
         let eth_provider = eth_client.eth_provider().clone();
 
         let alchemy_provider = Arc::new(AlchemyDataProvider::new(eth_provider.clone()));
         let pool_provider = Arc::new(PoolDataProvider::new(eth_client.clone()));
         let debug_provider = Arc::new(DebugDataProvider::new(eth_provider.clone()));
+        let kakarot_provider = Arc::new(KakarotDataProvider::new(eth_provider.inner().clone()));
+        let filter_provider = Arc::new(FilterDataProvider::new(eth_client.clone()));
 
-        let eth_rpc_module = EthRpc::new(eth_client).into_rpc();
+        let mut eth_rpc_module = EthRpc::new(eth_client.clone(), filter_provider).into_rpc();
+        eth_rpc_module
+            .merge(EthPubSubRpc::new(eth_client).into_rpc())
+            .expect("eth_subscribe and eth_unsubscribe are not Ethereum methods");
         let alchemy_rpc_module = AlchemyRpc::new(alchemy_provider).into_rpc();
         let web3_rpc_module = Web3Rpc::default().into_rpc();
         let net_rpc_module = NetRpc::new(eth_provider.clone()).into_rpc();
         let debug_rpc_module = DebugRpc::new(debug_provider).into_rpc();
         let trace_rpc_module = TraceRpc::new(eth_provider).into_rpc();
-        let kakarot_rpc_module = KakarotRpc.into_rpc();
+        let kakarot_rpc_module = KakarotRpc::new(kakarot_provider).into_rpc();
         let txpool_rpc_module = TxpoolRpc::new(pool_provider).into_rpc();
 
         let mut modules = HashMap::new();
@@ -69,14 +109,35 @@ where
         modules.insert(KakarotRpcModule::Txpool, txpool_rpc_module.into());
         modules.insert(KakarotRpcModule::KakarotRpc, kakarot_rpc_module.into());
 
-        Self { modules, _phantom: PhantomData }
+        Self { modules, config: RpcModulesConfig::default(), _phantom: PhantomData }
     }
 
+    /// Sets the modules and methods to serve, all of them are served by default.
+    #[must_use]
+    pub fn with_config(mut self, config: RpcModulesConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Merges the enabled modules, without the methods filtered out by the configuration.
     pub fn rpc_module(&self) -> Result<RpcModule<()>, RegisterMethodError> {
+        self.rpc_module_with_config(&self.config)
+    }
+
+    /// Merges the modules enabled by the given configuration, without the methods it filters out.
+    pub fn rpc_module_with_config(&self, config: &RpcModulesConfig) -> Result<RpcModule<()>, RegisterMethodError> {
         let mut rpc_module = RpcModule::new(());
 
-        for methods in self.modules.values().cloned() {
-            rpc_module.merge(methods)?;
+        for (module, methods) in &self.modules {
+            if config.is_module_enabled(*module) {
+                rpc_module.merge(methods.clone())?;
+            }
+        }
+
+        let filtered_methods: Vec<_> =
+            rpc_module.method_names().filter(|method| !config.is_method_allowed(method)).collect();
+        for method in filtered_methods {
+            rpc_module.remove_method(method);
         }
 
         Ok(rpc_module)
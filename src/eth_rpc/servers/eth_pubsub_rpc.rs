@@ -0,0 +1,267 @@
+use crate::{
+    client::{EthClient, TransactionHashProvider},
+    eth_rpc::api::eth_pubsub_api::EthPubSubApiServer,
+    providers::eth_provider::{
+        database::{ethereum::EthereumBlockStore, types::transaction::ExtendedTransaction},
+        error::{EthApiError, EthRpcErrorCode},
+        provider::EthApiResult,
+        BlockProvider, LogProvider,
+    },
+};
+use alloy_rpc_types::{
+    pubsub::{Params, SubscriptionKind, SubscriptionResult},
+    Filter, FilterChanges,
+};
+use jsonrpsee::{
+    core::{async_trait, SubscriptionResult as RpcSubscriptionResult},
+    types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use reth_transaction_pool::{TransactionListenerKind, TransactionPool};
+use starknet::providers::Provider;
+use std::{sync::Arc, time::Duration};
+
+/// Interval at which the database is polled for new blocks.
+const NEW_BLOCKS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A validated `eth_subscribe` request.
+#[derive(Debug)]
+enum Subscription {
+    /// New block headers.
+    NewHeads,
+    /// Logs matching the filter, included in new blocks.
+    Logs(Box<Filter>),
+    /// Transactions entering the mempool, as hashes or full transactions.
+    PendingTransactions { full: bool },
+}
+
+impl Subscription {
+    /// Validates the parameters of the subscription.
+    fn new(kind: SubscriptionKind, params: Option<Params>) -> Result<Self, ErrorObject<'static>> {
+        match (kind, params.unwrap_or_default()) {
+            (SubscriptionKind::NewHeads, Params::None) => Ok(Self::NewHeads),
+            (SubscriptionKind::Logs, Params::None) => Ok(Self::Logs(Box::default())),
+            (SubscriptionKind::Logs, Params::Logs(filter)) => Ok(Self::Logs(filter)),
+            (SubscriptionKind::NewPendingTransactions, Params::None) => Ok(Self::PendingTransactions { full: false }),
+            (SubscriptionKind::NewPendingTransactions, Params::Bool(full)) => Ok(Self::PendingTransactions { full }),
+            (SubscriptionKind::Syncing, _) => Err(EthApiError::Unsupported("eth_subscribe(syncing)").into()),
+            (kind, _) => Err(ErrorObject::owned(
+                EthRpcErrorCode::InvalidParams as i32,
+                format!("invalid params for {kind:?} subscription"),
+                None::<()>,
+            )),
+        }
+    }
+}
+
+/// The RPC module for the publish-subscribe methods of the Ethereum protocol,
+/// only available over WebSocket.
+#[derive(Debug)]
+pub struct EthPubSubRpc<SP>
+where
+    SP: Provider + Send + Sync,
+{
+    eth_client: Arc<EthClient<SP>>,
+}
+
+impl<SP> EthPubSubRpc<SP>
+where
+    SP: Provider + Send + Sync,
+{
+    pub const fn new(eth_client: Arc<EthClient<SP>>) -> Self {
+        Self { eth_client }
+    }
+}
+
+#[async_trait]
+impl<SP> EthPubSubApiServer for EthPubSubRpc<SP>
+where
+    SP: Provider + Clone + Send + Sync + 'static,
+{
+    #[tracing::instrument(skip(self, pending))]
+    async fn subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: SubscriptionKind,
+        params: Option<Params>,
+    ) -> RpcSubscriptionResult {
+        let subscription = match Subscription::new(kind, params) {
+            Ok(subscription) => subscription,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(());
+            }
+        };
+
+        // Only the blocks added after the subscription is accepted are streamed.
+        let last_block = match self.eth_client.eth_provider().block_number().await {
+            Ok(block_number) => block_number.to(),
+            Err(err) => {
+                pending.reject(ErrorObject::from(err)).await;
+                return Ok(());
+            }
+        };
+
+        let sink = pending.accept().await?;
+        let eth_client = self.eth_client.clone();
+
+        tokio::spawn(async move {
+            let result = match subscription {
+                Subscription::NewHeads => new_heads(&eth_client, &sink, last_block).await,
+                Subscription::Logs(filter) => logs(&eth_client, &sink, &filter, last_block).await,
+                Subscription::PendingTransactions { full } => pending_transactions(&eth_client, &sink, full).await,
+            };
+            if let Err(err) = result {
+                tracing::debug!(target: "rpc::eth::pubsub", %err, "subscription closed on error");
+            }
+        });
+
+        Ok(())
+    }
+}
+
+/// Streams the headers of the blocks after `last_block` until the subscription is closed.
+async fn new_heads<SP>(eth_client: &EthClient<SP>, sink: &SubscriptionSink, mut last_block: u64) -> EthApiResult<()>
+where
+    SP: Provider + Clone + Send + Sync,
+{
+    while let Some((from, to)) = wait_for_new_blocks(eth_client, sink, &mut last_block).await? {
+        let headers = eth_client.eth_provider().database().headers(from, to).await?;
+
+        // A zero hash corresponds to the pending block, which isn't a new block yet.
+        for header in headers.into_iter().filter(|header| !header.hash.is_zero()) {
+            if !send(sink, &SubscriptionResult::Header(Box::new(header))).await {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Streams the logs matching the filter in the blocks after `last_block` until the subscription
+/// is closed.
+async fn logs<SP>(
+    eth_client: &EthClient<SP>,
+    sink: &SubscriptionSink,
+    filter: &Filter,
+    mut last_block: u64,
+) -> EthApiResult<()>
+where
+    SP: Provider + Clone + Send + Sync,
+{
+    while let Some((from, to)) = wait_for_new_blocks(eth_client, sink, &mut last_block).await? {
+        // The block range of the filter is ignored, only new blocks are considered.
+        let filter = filter.clone().from_block(from).to_block(to);
+        let logs = match eth_client.eth_provider().get_logs(filter).await {
+            Ok(FilterChanges::Logs(logs)) => logs,
+            Ok(_) => continue,
+            // The logs of the new blocks can exceed the limits of the node, they are skipped
+            // instead of closing the subscription.
+            Err(err) => {
+                tracing::warn!(target: "rpc::eth::pubsub", %err, from, to, "failed to get the logs of the subscription");
+                continue;
+            }
+        };
+
+        for log in logs {
+            if !send(sink, &SubscriptionResult::Log(Box::new(log))).await {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Streams the transactions entering the mempool until the subscription is closed.
+async fn pending_transactions<SP>(eth_client: &EthClient<SP>, sink: &SubscriptionSink, full: bool) -> EthApiResult<()>
+where
+    SP: Provider + Clone + Send + Sync,
+{
+    let mut listener = eth_client.mempool().pending_transactions_listener_for(TransactionListenerKind::All);
+
+    loop {
+        let hash = tokio::select! {
+            () = sink.closed() => return Ok(()),
+            hash = listener.recv() => match hash {
+                Some(hash) => hash,
+                None => return Ok(()),
+            },
+        };
+
+        let item = if full {
+            // The transaction might have been mined or dropped in the meantime.
+            let Some(transaction) = eth_client.transaction_by_hash(hash).await? else {
+                continue;
+            };
+            SubscriptionResult::FullTransaction(Box::new(transaction))
+        } else {
+            SubscriptionResult::TransactionHash(hash)
+        };
+
+        if !send(sink, &item).await {
+            return Ok(());
+        }
+    }
+}
+
+/// Polls the database until blocks after `last_block` are available. Returns
+/// the range of the new blocks and moves `last_block` to its end, or `None`
+/// once the subscription is closed.
+async fn wait_for_new_blocks<SP>(
+    eth_client: &EthClient<SP>,
+    sink: &SubscriptionSink,
+    last_block: &mut u64,
+) -> EthApiResult<Option<(u64, u64)>>
+where
+    SP: Provider + Clone + Send + Sync,
+{
+    loop {
+        tokio::select! {
+            () = sink.closed() => return Ok(None),
+            () = tokio::time::sleep(NEW_BLOCKS_POLL_INTERVAL) => {}
+        }
+
+        let current_block: u64 = eth_client.eth_provider().block_number().await?.to();
+        if current_block > *last_block {
+            let range = (*last_block + 1, current_block);
+            *last_block = current_block;
+            return Ok(Some(range));
+        }
+    }
+}
+
+/// Sends the item to the subscriber. Returns false if the subscriber is gone.
+async fn send(sink: &SubscriptionSink, item: &SubscriptionResult<ExtendedTransaction>) -> bool {
+    match SubscriptionMessage::from_json(item) {
+        Ok(message) => sink.send(message).await.is_ok(),
+        Err(err) => {
+            tracing::error!(target: "rpc::eth::pubsub", %err, "failed to serialize subscription item");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_params() {
+        // Given
+        let filter = Filter::new().address(alloy_primitives::Address::ZERO);
+
+        // When
+        let logs = Subscription::new(SubscriptionKind::Logs, Some(Params::Logs(Box::new(filter.clone()))));
+        let full_transactions = Subscription::new(SubscriptionKind::NewPendingTransactions, Some(Params::Bool(true)));
+        let invalid = Subscription::new(SubscriptionKind::NewHeads, Some(Params::Bool(true)));
+        let syncing = Subscription::new(SubscriptionKind::Syncing, None);
+
+        // Then
+        assert!(matches!(logs, Ok(Subscription::Logs(f)) if *f == filter));
+        assert!(matches!(full_transactions, Ok(Subscription::PendingTransactions { full: true })));
+        assert_eq!(invalid.unwrap_err().code(), EthRpcErrorCode::InvalidParams as i32);
+        assert_eq!(syncing.unwrap_err().code(), EthRpcErrorCode::InternalError as i32);
+    }
+}
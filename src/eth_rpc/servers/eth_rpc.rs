@@ -1,19 +1,27 @@
 use crate::{
     client::{EthClient, TransactionHashProvider},
     eth_rpc::api::eth_api::EthApiServer,
-    providers::eth_provider::{
-        constant::MAX_PRIORITY_FEE_PER_GAS,
-        database::types::{header::ExtendedBlock, receipt::ExtendedTxReceipt, transaction::ExtendedTransaction},
-        error::EthApiError,
-        BlockProvider, ChainProvider, GasProvider, LogProvider, ReceiptProvider, StateProvider, TransactionProvider,
+    models::bundle::{CallBundle, CallBundleResponse},
+    providers::{
+        eth_provider::{
+            constant::MAX_PRIORITY_FEE_PER_GAS,
+            database::types::{header::ExtendedBlock, receipt::ExtendedTxReceipt, transaction::ExtendedTransaction},
+            error::EthApiError,
+            BlockProvider, ChainProvider, GasProvider, LogProvider, StateProvider, TransactionProvider,
+        },
+        filter_provider::{FilterDataProvider, FilterProvider},
     },
 };
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, Bytes, B256, B64, U256, U64};
 use alloy_rpc_types::{
-    serde_helpers::JsonStorageKey, state::StateOverride, AccessListResult, BlockOverrides, EIP1186AccountProofResponse,
-    FeeHistory, Filter, FilterChanges, Index, SyncStatus, TransactionRequest, Work,
+    serde_helpers::JsonStorageKey,
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+    AccessListResult, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Filter,
+    FilterChanges, Index, StateContext, SyncStatus, TransactionRequest, Work,
 };
+use alloy_serde::WithOtherFields;
 use jsonrpsee::core::{async_trait, RpcResult};
 use serde_json::Value;
 use starknet::providers::Provider;
@@ -27,14 +35,15 @@ where
     SP: Provider + Send + Sync,
 {
     eth_client: Arc<EthClient<SP>>,
+    filter_provider: Arc<FilterDataProvider<SP>>,
 }
 
 impl<SP> EthRpc<SP>
 where
     SP: Provider + Send + Sync,
 {
-    pub const fn new(eth_client: Arc<EthClient<SP>>) -> Self {
-        Self { eth_client }
+    pub const fn new(eth_client: Arc<EthClient<SP>>, filter_provider: Arc<FilterDataProvider<SP>>) -> Self {
+        Self { eth_client, filter_provider }
     }
 }
 
@@ -136,7 +145,7 @@ where
 
     #[tracing::instrument(skip(self), ret, err)]
     async fn transaction_receipt(&self, hash: B256) -> RpcResult<Option<ExtendedTxReceipt>> {
-        Ok(self.eth_client.eth_provider().transaction_receipt(hash).await?)
+        Ok(self.eth_client.transaction_receipt(hash).await?)
     }
 
     #[tracing::instrument(skip(self), ret, err)]
@@ -176,17 +185,47 @@ where
         Ok(self.eth_client.eth_provider().call(request, block_id, state_overrides, block_overrides).await?)
     }
 
+    #[tracing::instrument(skip(self, payload), err)]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock<ExtendedBlock>>> {
+        Ok(self.eth_client.eth_provider().simulate_v1(payload, block_id).await?)
+    }
+
+    #[tracing::instrument(skip(self, bundle), err)]
+    async fn call_many(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<Vec<EthCallResponse>> {
+        Ok(self.eth_client.eth_provider().call_many(bundle, state_context, state_overrides).await?)
+    }
+
+    #[tracing::instrument(skip(self, bundle), err)]
+    async fn call_bundle(&self, bundle: CallBundle) -> RpcResult<CallBundleResponse> {
+        Ok(self.eth_client.eth_provider().call_bundle(bundle).await?)
+    }
+
+    #[tracing::instrument(skip(self, request), err)]
     async fn create_access_list(
         &self,
-        _request: TransactionRequest,
-        _block_id: Option<BlockId>,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
     ) -> RpcResult<AccessListResult> {
-        Err(EthApiError::Unsupported("eth_createAccessList").into())
+        Ok(self.eth_client.eth_provider().create_access_list(request, block_id).await?)
     }
 
     #[tracing::instrument(skip(self, request), err)]
-    async fn estimate_gas(&self, request: TransactionRequest, block_id: Option<BlockId>) -> RpcResult<U256> {
-        Ok(U256::from(self.eth_client.eth_provider().estimate_gas(request, block_id).await?))
+    async fn estimate_gas(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> RpcResult<U256> {
+        Ok(U256::from(self.eth_client.eth_provider().estimate_gas(request, block_id, state_overrides).await?))
     }
 
     #[tracing::instrument(skip_all, ret, err)]
@@ -279,40 +318,48 @@ where
         Err(EthApiError::Unsupported("eth_signTypedData").into())
     }
 
+    #[tracing::instrument(skip(self), err)]
     async fn get_proof(
         &self,
-        _address: Address,
-        _keys: Vec<B256>,
-        _block_id: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse> {
-        Err(EthApiError::Unsupported("eth_getProof").into())
+        address: Address,
+        keys: Vec<B256>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<WithOtherFields<EIP1186AccountProofResponse>> {
+        Ok(self.eth_client.eth_provider().get_proof(address, keys, block_id).await?)
     }
 
-    async fn new_filter(&self, _filter: Filter) -> RpcResult<U64> {
-        Err(EthApiError::Unsupported("eth_newFilter").into())
+    #[tracing::instrument(skip_all, ret, err)]
+    async fn new_filter(&self, filter: Filter) -> RpcResult<U64> {
+        tracing::info!(?filter);
+        Ok(self.filter_provider.new_filter(filter).await?)
     }
 
+    #[tracing::instrument(skip_all, ret, err)]
     async fn new_block_filter(&self) -> RpcResult<U64> {
-        Err(EthApiError::Unsupported("eth_newBlockFilter").into())
+        Ok(self.filter_provider.new_block_filter().await?)
     }
 
+    #[tracing::instrument(skip_all, ret, err)]
     async fn new_pending_transaction_filter(&self) -> RpcResult<U64> {
-        Err(EthApiError::Unsupported("eth_newPendingTransactionFilter").into())
+        Ok(self.filter_provider.new_pending_transaction_filter().await?)
     }
 
-    async fn uninstall_filter(&self, _id: U64) -> RpcResult<bool> {
-        Err(EthApiError::Unsupported("eth_uninstallFilter").into())
+    #[tracing::instrument(skip(self), ret, err)]
+    async fn uninstall_filter(&self, id: U64) -> RpcResult<bool> {
+        Ok(self.filter_provider.uninstall_filter(id).await?)
     }
 
-    async fn get_filter_changes(&self, _id: U64) -> RpcResult<FilterChanges> {
-        Err(EthApiError::Unsupported("eth_getFilterChanges").into())
+    #[tracing::instrument(skip(self), err)]
+    async fn get_filter_changes(&self, id: U64) -> RpcResult<FilterChanges> {
+        Ok(self.filter_provider.filter_changes(id).await?)
     }
 
-    async fn get_filter_logs(&self, _id: U64) -> RpcResult<FilterChanges> {
-        Err(EthApiError::Unsupported("eth_getFilterLogs").into())
+    #[tracing::instrument(skip(self), err)]
+    async fn get_filter_logs(&self, id: U64) -> RpcResult<FilterChanges> {
+        Ok(self.filter_provider.filter_logs(id).await?)
     }
 
     async fn block_receipts(&self, block_id: Option<BlockId>) -> RpcResult<Option<Vec<ExtendedTxReceipt>>> {
-        Ok(self.eth_client.eth_provider().block_receipts(block_id).await?)
+        Ok(self.eth_client.block_receipts(block_id).await?)
     }
 }
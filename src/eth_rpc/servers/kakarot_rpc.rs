@@ -1,26 +1,81 @@
 use crate::{
-    config::KakarotRpcConfig,
+    config::{Config, KakarotRpcConfig},
     eth_rpc::api::kakarot_api::KakarotApiServer,
-    providers::eth_provider::{
-        constant::{Constant, MAX_LOGS},
-        starknet::kakarot_core::{get_white_listed_eip_155_transaction_hashes, MAX_FELTS_IN_CALLDATA},
+    models::kakarot::{AccountClassHashes, AccountStatus},
+    providers::{
+        eth_provider::{constant::Constant, error::EthRpcErrorCode},
+        kakarot_provider::KakarotProvider,
     },
 };
-use jsonrpsee::core::{async_trait, RpcResult};
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types::BlockId;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    types::ErrorObject,
+};
+use starknet::core::types::Felt;
 
 #[derive(Debug)]
-pub struct KakarotRpc;
+pub struct KakarotRpc<KP: KakarotProvider> {
+    kakarot_provider: KP,
+}
+
+impl<KP> KakarotRpc<KP>
+where
+    KP: KakarotProvider,
+{
+    pub const fn new(kakarot_provider: KP) -> Self {
+        Self { kakarot_provider }
+    }
+}
 
 #[async_trait]
-impl KakarotApiServer for KakarotRpc {
+impl<KP> KakarotApiServer for KakarotRpc<KP>
+where
+    KP: KakarotProvider + Send + Sync + 'static,
+{
     async fn get_config(&self) -> RpcResult<Constant> {
-        let starknet_config = KakarotRpcConfig::from_env().expect("Failed to load Kakarot RPC config");
+        let internal_error =
+            |err: eyre::Report| ErrorObject::owned(EthRpcErrorCode::InternalError as i32, err.to_string(), None::<()>);
+        let config = Config::current().map_err(internal_error)?;
+        let starknet_config = KakarotRpcConfig::from_config(&config).map_err(internal_error)?;
         Ok(Constant {
-            max_logs: *MAX_LOGS,
+            max_logs: config.max_logs,
             starknet_network: String::from(starknet_config.network_url),
-            max_felts_in_calldata: *MAX_FELTS_IN_CALLDATA,
-            white_listed_eip_155_transaction_hashes: get_white_listed_eip_155_transaction_hashes(),
+            max_felts_in_calldata: config.max_felts_in_calldata.unwrap_or_default(),
+            white_listed_eip_155_transaction_hashes: config
+                .white_listed_eip_155_transaction_hashes
+                .clone()
+                .unwrap_or_default(),
             kakarot_address: starknet_config.kakarot_address,
         })
     }
+
+    async fn get_eth_transaction_hash(&self, starknet_hash: Felt) -> RpcResult<Option<B256>> {
+        Ok(self.kakarot_provider.eth_transaction_hash(starknet_hash).await?)
+    }
+
+    async fn get_starknet_transaction_hash(&self, eth_hash: B256) -> RpcResult<Option<Felt>> {
+        Ok(self.kakarot_provider.starknet_transaction_hash(eth_hash).await?)
+    }
+
+    async fn get_starknet_address(&self, address: Address) -> RpcResult<Felt> {
+        Ok(self.kakarot_provider.starknet_address(address))
+    }
+
+    async fn get_account_status(&self, address: Address, block_id: Option<BlockId>) -> RpcResult<AccountStatus> {
+        Ok(self.kakarot_provider.account_status(address, block_id).await?)
+    }
+
+    async fn get_base_fee(&self, block_id: Option<BlockId>) -> RpcResult<U256> {
+        Ok(self.kakarot_provider.base_fee(block_id).await?)
+    }
+
+    async fn get_coinbase(&self, block_id: Option<BlockId>) -> RpcResult<Address> {
+        Ok(self.kakarot_provider.coinbase(block_id).await?)
+    }
+
+    async fn get_account_class_hashes(&self, block_id: Option<BlockId>) -> RpcResult<AccountClassHashes> {
+        Ok(self.kakarot_provider.account_class_hashes(block_id).await?)
+    }
 }
@@ -1,5 +1,6 @@
 pub mod alchemy_rpc;
 pub mod debug_rpc;
+pub mod eth_pubsub_rpc;
 pub mod eth_rpc;
 pub mod kakarot_rpc;
 pub mod net_rpc;
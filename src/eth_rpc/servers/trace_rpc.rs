@@ -1,12 +1,28 @@
 use crate::{
-    eth_rpc::api::trace_api::TraceApiServer, providers::eth_provider::provider::EthereumProvider,
-    tracing::builder::TracerBuilder,
+    eth_rpc::api::trace_api::TraceApiServer,
+    providers::eth_provider::{
+        error::{EthApiError, TransactionError},
+        provider::EthereumProvider,
+    },
+    tracing::{
+        builder::{Pinned, TracerBuilder},
+        Tracer,
+    },
 };
-use alloy_rpc_types::BlockId;
-use alloy_rpc_types_trace::parity::LocalizedTransactionTrace;
+use alloy_primitives::B256;
+use alloy_rpc_types::{BlockId, Index, TransactionRequest};
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    parity::{LocalizedTransactionTrace, TraceResults, TraceResultsWithTransactionHash, TraceType},
+};
+use eyre::eyre;
 use jsonrpsee::core::{async_trait, RpcResult};
+use reth_rpc_eth_types::EthApiError as RethEthApiError;
 use revm_inspectors::tracing::TracingInspectorConfig;
-use std::sync::Arc;
+use std::collections::HashSet;
+
+/// The maximum number of blocks that can be traced by a single `trace_filter` request.
+const MAX_TRACE_FILTER_BLOCK_RANGE: u64 = 100;
 
 /// The RPC module for implementing the Trace api
 #[derive(Debug)]
@@ -20,19 +36,187 @@ impl<P: EthereumProvider> TraceRpc<P> {
     }
 }
 
+impl<P: EthereumProvider + Clone + Send + Sync + 'static> TraceRpc<P> {
+    /// Returns a tracer builder pinned to the given block.
+    async fn block_tracer_builder(&self, block_id: BlockId) -> RpcResult<TracerBuilder<P, Pinned>> {
+        Ok(TracerBuilder::new(self.eth_provider.clone()).await?.with_block_id(block_id).await?)
+    }
+
+    /// Returns a tracer builder pinned to the block of the given transaction.
+    async fn transaction_tracer_builder(&self, transaction_hash: B256) -> RpcResult<TracerBuilder<P, Pinned>> {
+        Ok(TracerBuilder::new(self.eth_provider.clone()).await?.with_transaction_hash(transaction_hash).await?)
+    }
+
+    /// Returns a tracer executing calls on top of the state of the given block, defaulting to the latest block.
+    async fn call_tracer(&self, block_id: Option<BlockId>) -> RpcResult<Tracer<P>> {
+        Ok(self.block_tracer_builder(block_id.unwrap_or_default()).await?.build_for_call())
+    }
+}
+
 #[async_trait]
-impl<P: EthereumProvider + Send + Sync + 'static> TraceApiServer for TraceRpc<P> {
+impl<P: EthereumProvider + Clone + Send + Sync + 'static> TraceApiServer for TraceRpc<P> {
     /// Returns the parity traces for the given block.
     #[tracing::instrument(skip(self), err)]
     async fn trace_block(&self, block_id: BlockId) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
         tracing::info!("Serving debug_traceBlock");
-        let tracer = TracerBuilder::new(Arc::new(&self.eth_provider))
-            .await?
-            .with_block_id(block_id)
+        let tracer = self
+            .block_tracer_builder(block_id)
             .await?
             .with_tracing_options(TracingInspectorConfig::default_parity().into())
             .build()?;
 
         Ok(tracer.trace_block()?)
     }
+
+    /// Returns the parity traces for the given transaction.
+    #[tracing::instrument(skip(self), err)]
+    async fn trace_transaction(&self, transaction_hash: B256) -> RpcResult<Option<Vec<LocalizedTransactionTrace>>> {
+        tracing::info!("Serving trace_transaction");
+        let tracer = self
+            .transaction_tracer_builder(transaction_hash)
+            .await?
+            .with_tracing_options(TracingInspectorConfig::default_parity().into())
+            .build()?;
+
+        Ok(tracer.trace_transaction(transaction_hash)?)
+    }
+
+    /// Returns the parity trace at the given position in the traces of the transaction.
+    #[tracing::instrument(skip(self), err)]
+    async fn trace_get(&self, hash: B256, indices: Vec<Index>) -> RpcResult<Option<LocalizedTransactionTrace>> {
+        tracing::info!("Serving trace_get");
+        // Parity only supports a single index.
+        let [index] = indices[..] else {
+            return Ok(None);
+        };
+
+        let traces = self.trace_transaction(hash).await?;
+        Ok(traces.and_then(|traces| traces.into_iter().nth(index.into())))
+    }
+
+    /// Executes the call on top of the state of the given block and returns the requested traces.
+    #[tracing::instrument(skip(self, request), err)]
+    async fn trace_call(
+        &self,
+        request: TransactionRequest,
+        trace_types: HashSet<TraceType>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<TraceResults> {
+        tracing::info!("Serving trace_call");
+        let tracer = self.call_tracer(block_id).await?;
+
+        let results = tracer.trace_calls(vec![(request, trace_types)])?;
+        Ok(results.into_iter().next().ok_or(TransactionError::Tracing(eyre!("No trace found").into()))?)
+    }
+
+    /// Executes the calls one after the other on top of the state of the given block and
+    /// returns the requested traces for each of them.
+    #[tracing::instrument(skip(self, calls), err)]
+    async fn trace_call_many(
+        &self,
+        calls: Vec<(TransactionRequest, HashSet<TraceType>)>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<TraceResults>> {
+        tracing::info!("Serving trace_callMany");
+        let tracer = self.call_tracer(block_id).await?;
+
+        Ok(tracer.trace_calls(calls)?)
+    }
+
+    /// Replays the given transaction and returns the requested traces.
+    #[tracing::instrument(skip(self), err)]
+    async fn trace_replay_transaction(
+        &self,
+        transaction_hash: B256,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<TraceResults> {
+        tracing::info!("Serving trace_replayTransaction");
+        let tracer = self.transaction_tracer_builder(transaction_hash).await?.build()?;
+
+        Ok(tracer.replay_transaction(transaction_hash, &trace_types)?)
+    }
+
+    /// Replays all the transactions of the given block and returns the requested traces.
+    #[tracing::instrument(skip(self), err)]
+    async fn trace_replay_block_transactions(
+        &self,
+        block_id: BlockId,
+        trace_types: HashSet<TraceType>,
+    ) -> RpcResult<Option<Vec<TraceResultsWithTransactionHash>>> {
+        tracing::info!("Serving trace_replayBlockTransactions");
+        let tracer = self.block_tracer_builder(block_id).await?.build()?;
+
+        Ok(Some(tracer.replay_block(&trace_types)?))
+    }
+
+    /// Returns the parity traces matching the given filter.
+    #[tracing::instrument(skip(self), err)]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>> {
+        tracing::info!("Serving trace_filter");
+        let to = match filter.to_block {
+            Some(to) => to,
+            None => self.eth_provider.block_number().await?.to(),
+        };
+        let (from, to) = trace_filter_block_range(filter.from_block, to).map_err(EthApiError::from)?;
+
+        let matcher = filter.matcher();
+        let mut traces = Vec::new();
+        for block_number in from..=to {
+            let tracer = self
+                .block_tracer_builder(block_number.into())
+                .await?
+                .with_tracing_options(TracingInspectorConfig::default_parity().into())
+                .build()?;
+            let block_traces = tracer.trace_block()?.unwrap_or_default();
+            traces.extend(block_traces.into_iter().filter(|trace| matcher.matches(&trace.trace)));
+        }
+
+        let after = filter.after.unwrap_or_default() as usize;
+        let count = filter.count.map_or(usize::MAX, |count| count as usize);
+        Ok(traces.into_iter().skip(after).take(count).collect())
+    }
+}
+
+/// Returns the block range traced by `trace_filter`. Without `fromBlock`, the range covers the
+/// last [`MAX_TRACE_FILTER_BLOCK_RANGE`] blocks up to `toBlock`.
+fn trace_filter_block_range(from: Option<u64>, to: u64) -> Result<(u64, u64), RethEthApiError> {
+    let from = from.unwrap_or_else(|| to.saturating_sub(MAX_TRACE_FILTER_BLOCK_RANGE - 1));
+
+    if from > to {
+        return Err(RethEthApiError::InvalidParams(
+            "invalid parameters: fromBlock cannot be greater than toBlock".to_string(),
+        ));
+    }
+    if to - from >= MAX_TRACE_FILTER_BLOCK_RANGE {
+        return Err(RethEthApiError::InvalidParams(format!(
+            "block range too large; currently limited to {MAX_TRACE_FILTER_BLOCK_RANGE} blocks"
+        )));
+    }
+
+    Ok((from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_filter_block_range() {
+        // Given
+        let latest = 1_000;
+
+        // When
+        let default_from = trace_filter_block_range(None, latest);
+        let default_from_near_genesis = trace_filter_block_range(None, 10);
+        let explicit = trace_filter_block_range(Some(901), latest);
+        let too_large = trace_filter_block_range(Some(900), latest);
+        let inverted = trace_filter_block_range(Some(latest + 1), latest);
+
+        // Then
+        assert_eq!(default_from.unwrap(), (901, latest));
+        assert_eq!(default_from_near_genesis.unwrap(), (0, 10));
+        assert_eq!(explicit.unwrap(), (901, latest));
+        assert!(too_large.unwrap_err().to_string().contains("block range too large"));
+        assert!(inverted.unwrap_err().to_string().contains("fromBlock cannot be greater than toBlock"));
+    }
 }
@@ -7,8 +7,11 @@ use tracing_subscriber as _;
 
 pub mod providers {
     pub mod alchemy_provider;
+    pub mod cache_provider;
     pub mod debug_provider;
     pub mod eth_provider;
+    pub mod filter_provider;
+    pub mod kakarot_provider;
     pub mod pool_provider;
     pub mod sn_provider;
 }
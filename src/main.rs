@@ -1,84 +1,161 @@
+use clap::{CommandFactory, FromArgMatches, Parser};
 use dotenvy::dotenv;
 use eyre::Result;
 use kakarot_rpc::{
     client::EthClient,
-    constants::{KAKAROT_RPC_CONFIG, KKRT_BLOCK_GAS_LIMIT, RPC_CONFIG},
-    eth_rpc::{rpc::KakarotRpcModuleBuilder, run_server},
+    config::{ignore_empty_env, Config, KakarotRpcConfig},
+    constants::KKRT_BLOCK_GAS_LIMIT,
+    eth_rpc::{
+        config::{AdminRPCConfig, RPCConfig, RpcModulesConfig},
+        rpc::KakarotRpcModuleBuilder,
+        run_admin_server, run_server, RpcServerMetrics,
+    },
     pool::{
         constants::PRUNE_DURATION,
         mempool::{maintain_transaction_pool, AccountManager},
     },
-    providers::eth_provider::{
-        database::Database,
-        starknet::kakarot_core::{core::KakarotCoreReader, KAKAROT_ADDRESS},
+    prometheus_handler::{init_prometheus, Registry},
+    providers::{
+        cache_provider::{CacheConfig, CacheMetrics},
+        eth_provider::{database::Database, starknet::kakarot_core::core::KakarotCoreReader},
     },
 };
 use mongodb::options::{DatabaseOptions, ReadConcern, WriteConcern};
 use opentelemetry_sdk::runtime::Tokio;
 use reth_transaction_pool::PoolConfig;
 use starknet::{
-    core::types::{BlockId, BlockTag, Felt},
+    core::types::{BlockId, BlockTag},
     providers::{jsonrpc::HttpTransport, JsonRpcClient},
 };
-use std::{env::var, str::FromStr, sync::Arc};
+use std::{
+    env::var,
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+};
 use tracing_opentelemetry::MetricsLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Environment variables are safe to use after this
+/// Kakarot RPC node.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Path of the TOML configuration file, which values are overridden by the environment and the arguments.
+    #[arg(long, env = "KAKAROT_RPC_CONFIG_FILE")]
+    config: Option<PathBuf>,
+    /// Print the resolved configuration and exit.
+    #[arg(long)]
+    print_config: bool,
+    #[command(flatten)]
+    overrides: Config,
+}
+
+fn main() -> Result<()> {
     dotenv().ok();
 
+    // Resolve, validate and install the configuration before starting the runtime, empty
+    // environment variables being treated as unset
+    let cli = Cli::from_arg_matches(&Cli::command().mut_args(ignore_empty_env).get_matches())?;
+    let file_config = cli.config.as_deref().map(Config::from_file).transpose()?.unwrap_or_default();
+    let config = cli.overrides.merge(file_config);
+    config.validate()?;
+    if cli.print_config {
+        print!("{}", toml::to_string_pretty(&config.redacted())?);
+        return Ok(());
+    }
+    config.clone().install()?;
+
+    tokio::runtime::Builder::new_multi_thread().enable_all().build()?.block_on(run(config))
+}
+
+/// Runs the node with the validated configuration.
+async fn run(config: Config) -> Result<()> {
     setup_tracing().expect("failed to start tracing and metrics");
 
-    let starknet_provider = JsonRpcClient::new(HttpTransport::new(KAKAROT_RPC_CONFIG.network_url.clone()));
+    let starknet_config = KakarotRpcConfig::from_config(&config)?;
+    let starknet_provider = JsonRpcClient::new(HttpTransport::new(starknet_config.network_url));
 
     // Setup the database
     let db_client =
-        mongodb::Client::with_uri_str(var("MONGO_CONNECTION_STRING").expect("Missing MONGO_CONNECTION_STRING .env"))
-            .await?;
+        mongodb::Client::with_uri_str(config.mongo_connection_string.as_deref().unwrap_or_default()).await?;
     let db = Database::new(
         db_client.database_with_options(
-            &var("MONGO_DATABASE_NAME").expect("Missing MONGO_DATABASE_NAME from .env"),
+            config.mongo_database_name.as_deref().unwrap_or_default(),
             DatabaseOptions::builder()
                 .read_concern(ReadConcern::majority())
                 .write_concern(WriteConcern::majority())
                 .build(),
         ),
     );
+    db.ensure_indexes(config.mongo_indexes.unwrap_or_default()).await?;
 
     // Setup the eth provider
     let starknet_provider = Arc::new(starknet_provider);
 
     // Get the pool config
-    let contract_reader = KakarotCoreReader::new(*KAKAROT_ADDRESS, starknet_provider.clone());
+    let contract_reader = KakarotCoreReader::new(starknet_config.kakarot_address, starknet_provider.clone());
     let base_fee = contract_reader.get_base_fee().block_id(BlockId::Tag(BlockTag::Pending)).call().await?.base_fee;
     let base_fee = base_fee.try_into()?;
-    let config =
+    let pool_config =
         PoolConfig { minimal_protocol_basefee: base_fee, gas_limit: KKRT_BLOCK_GAS_LIMIT, ..Default::default() };
 
+    // Creating the prometheus registry to register the metrics
+    let registry = Registry::new();
+
     // Init the Ethereum Client
-    let eth_client = EthClient::new(starknet_provider, config, db.clone());
+    let cache_metrics = CacheMetrics::new(Some(&registry))?;
+    let eth_client = EthClient::new(starknet_provider, pool_config, db.clone())
+        .with_cache(CacheConfig::from_config(&config), cache_metrics);
     let eth_client = Arc::new(eth_client);
 
     // Start the relayer manager
-    let addresses =
-        var("RELAYERS_ADDRESSES")?.split(',').filter_map(|addr| Felt::from_str(addr).ok()).collect::<Vec<_>>();
+    let addresses = config.relayers_addresses.clone().unwrap_or_default();
     AccountManager::new(addresses, Arc::clone(&eth_client)).start();
 
     // Start the maintenance of the mempool
     maintain_transaction_pool(Arc::clone(&eth_client), PRUNE_DURATION);
 
-    // Setup the RPC module
-    let kakarot_rpc_module = KakarotRpcModuleBuilder::new(eth_client).rpc_module()?;
+    // Setup the RPC modules, the admin modules are only served by the admin RPC server when it is enabled
+    let admin_rpc_config = AdminRPCConfig::from_config(&config)?;
+    let mut modules_config = RpcModulesConfig::from_config(&config);
+    if let Some(admin_modules) =
+        admin_rpc_config.as_ref().and_then(|admin_rpc_config| admin_rpc_config.modules.modules.as_ref())
+    {
+        modules_config.exclude_modules(admin_modules);
+    }
+    let rpc_module_builder = KakarotRpcModuleBuilder::new(eth_client).with_config(modules_config);
+    let kakarot_rpc_module = rpc_module_builder.rpc_module()?;
+    let rpc_config = RPCConfig::from_config(&config)?;
+
+    // Register the metrics of the RPC servers and serve the prometheus metrics so that they can be read
+    let server_metrics = RpcServerMetrics::new(&registry)?;
+    let prometheus_addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), config.prometheus_port.unwrap_or(9615));
+    tokio::spawn(async move {
+        let _ = init_prometheus(prometheus_addr, registry).await;
+    });
+
+    // Start the admin RPC server, if configured
+    let admin_server_handle = match admin_rpc_config {
+        Some(admin_rpc_config) => {
+            let admin_rpc_module = rpc_module_builder.rpc_module_with_config(&admin_rpc_config.modules)?;
+            let (socket_addr, handle) =
+                run_admin_server(admin_rpc_module, admin_rpc_config, rpc_config.limits, server_metrics.clone()).await?;
+            tracing::info!("Admin RPC Server running on http://{socket_addr}...");
+            Some(handle)
+        }
+        None => None,
+    };
 
     // Start the RPC server
-    let (socket_addr, server_handle) = run_server(kakarot_rpc_module, RPC_CONFIG.clone()).await?;
+    let (socket_addr, server_handle) = run_server(kakarot_rpc_module, rpc_config, server_metrics).await?;
     let url = format!("http://{socket_addr}");
 
     tracing::info!("RPC Server running on {url}...");
 
     server_handle.stopped().await;
+    if let Some(admin_server_handle) = admin_server_handle {
+        let _ = admin_server_handle.stop();
+    }
 
     Ok(())
 }
@@ -0,0 +1,77 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// Represents a bundle of signed transactions executed on top of a block by `eth_callBundle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundle {
+    /// The raw signed transactions of the bundle.
+    pub txs: Vec<Bytes>,
+    /// The number of the block the bundle is executed in.
+    pub block_number: U64,
+    /// The block which state the bundle is executed on top of.
+    pub state_block_number: BlockNumberOrTag,
+    /// The coinbase of the block, defaults to the coinbase of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// The timestamp of the block, defaults to the timestamp of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<U64>,
+    /// The gas limit of the block, defaults to the gas limit of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    /// The base fee of the block, defaults to the base fee of the state block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<U256>,
+}
+
+/// Represents the result of the execution of a transaction of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleTransactionResult {
+    /// The hash of the transaction.
+    pub tx_hash: B256,
+    /// The sender of the transaction.
+    pub from_address: Address,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to_address: Option<Address>,
+    /// The gas used by the transaction.
+    pub gas_used: U64,
+    /// The priority fee per gas paid to the coinbase.
+    pub gas_price: U256,
+    /// The priority fees paid to the coinbase.
+    pub gas_fees: U256,
+    /// The increase of the balance of the coinbase.
+    pub coinbase_diff: U256,
+    /// The value sent to the coinbase, on top of the priority fees.
+    pub eth_sent_to_coinbase: U256,
+    /// The output of the transaction, if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// The revert reason of the transaction, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+}
+
+/// Represents the result of the execution of a bundle by `eth_callBundle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResponse {
+    /// The hash of the bundle, the keccak256 of the concatenated transaction hashes.
+    pub bundle_hash: B256,
+    /// The average price per gas paid to the coinbase.
+    pub bundle_gas_price: U256,
+    /// The increase of the balance of the coinbase.
+    pub coinbase_diff: U256,
+    /// The value sent to the coinbase, on top of the priority fees.
+    pub eth_sent_to_coinbase: U256,
+    /// The priority fees paid to the coinbase.
+    pub gas_fees: U256,
+    /// The results of the transactions of the bundle.
+    pub results: Vec<CallBundleTransactionResult>,
+    /// The number of the block which state the bundle was executed on top of.
+    pub state_block_number: U64,
+    /// The gas used by all the transactions of the bundle.
+    pub total_gas_used: U64,
+}
@@ -0,0 +1,27 @@
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::Felt;
+
+/// Represents the Starknet account backing an EVM address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountStatus {
+    /// The EVM address of the account.
+    pub evm_address: Address,
+    /// The Starknet address of the account.
+    pub starknet_address: Felt,
+    /// Whether the account is deployed on Starknet.
+    pub deployed: bool,
+    /// Whether the deployed account is initialized.
+    pub initialized: bool,
+    /// The class hash of the account, if deployed.
+    pub class_hash: Option<Felt>,
+}
+
+/// Represents the class hashes of the accounts deployed by Kakarot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountClassHashes {
+    /// The class hash of the initialized accounts.
+    pub account_contract_class_hash: Felt,
+    /// The class hash of the accounts before their initialization.
+    pub uninitialized_account_class_hash: Felt,
+}
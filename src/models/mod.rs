@@ -1,4 +1,6 @@
 pub mod block;
+pub mod bundle;
 pub mod felt;
+pub mod kakarot;
 pub mod token;
 pub mod transaction;
@@ -0,0 +1,643 @@
+use crate::{
+    config::Config,
+    models::bundle::{CallBundle, CallBundleResponse},
+    prometheus_handler::{register, CounterVec, Opts, PrometheusError, Registry, U64 as U64Counter},
+    providers::eth_provider::{
+        database::types::{header::ExtendedBlock, receipt::ExtendedTxReceipt, transaction::ExtendedTransaction},
+        provider::{EthApiResult, EthereumProvider},
+        BlockProvider, ChainProvider, GasProvider, LogProvider, ReceiptProvider, StateProvider, TransactionProvider,
+    },
+};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use alloy_rpc_types::{
+    serde_helpers::JsonStorageKey,
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+    AccessListResult, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Filter,
+    FilterChanges, Header, Index, StateContext, SyncStatus, TransactionRequest,
+};
+use alloy_serde::WithOtherFields;
+use async_trait::async_trait;
+use schnellru::{ByLength, LruMap};
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Number of blocks below the latest block from which the hash of a block is served from the cache
+/// by number. A block closer to the head can still be replaced by another block at the same height.
+const CACHED_HASH_DEPTH: u64 = 64;
+
+/// Configuration of the caches of the [`CachedEthProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Maximum number of cached blocks, and of cached headers and block receipts.
+    pub max_blocks: u32,
+    /// Maximum number of cached transactions, and of cached receipts.
+    pub max_transactions: u32,
+    /// Maximum number of cached balances, nonces and storage values.
+    pub max_state_entries: u32,
+    /// Maximum number of cached bytecodes of accounts, which are up to 24 KiB each.
+    pub max_code_entries: u32,
+}
+
+impl CacheConfig {
+    /// Default sizes of the caches.
+    pub const DEFAULT: Self =
+        Self { max_blocks: 1_000, max_transactions: 10_000, max_state_entries: 10_000, max_code_entries: 1_000 };
+
+    /// Caches storing nothing.
+    pub const DISABLED: Self = Self { max_blocks: 0, max_transactions: 0, max_state_entries: 0, max_code_entries: 0 };
+
+    /// Returns the sizes of the caches of the configuration (0 disables a cache), using the
+    /// defaults for the missing ones.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_blocks: config.cache_max_blocks.unwrap_or(Self::DEFAULT.max_blocks),
+            max_transactions: config.cache_max_transactions.unwrap_or(Self::DEFAULT.max_transactions),
+            max_state_entries: config.cache_max_state_entries.unwrap_or(Self::DEFAULT.max_state_entries),
+            max_code_entries: config.cache_max_code_entries.unwrap_or(Self::DEFAULT.max_code_entries),
+        }
+    }
+}
+
+/// Metrics of the caches of the [`CachedEthProvider`].
+#[derive(Debug, Clone)]
+pub struct CacheMetrics {
+    /// Number of requests served from the cache, by kind of data.
+    hits: CounterVec<U64Counter>,
+    /// Number of cacheable requests forwarded to the inner provider, by kind of data.
+    misses: CounterVec<U64Counter>,
+}
+
+impl CacheMetrics {
+    /// Create an instance of metrics
+    pub fn new(metrics_registry: Option<&Registry>) -> Result<Option<Self>, PrometheusError> {
+        if let Some(metrics_registry) = metrics_registry {
+            Ok(Some(Self {
+                hits: register(
+                    CounterVec::new(
+                        Opts::new("eth_provider_cache_hits", "Number of requests served from the cache"),
+                        &["kind"],
+                    )?,
+                    metrics_registry,
+                )?,
+                misses: register(
+                    CounterVec::new(
+                        Opts::new("eth_provider_cache_misses", "Number of cacheable requests missing the cache"),
+                        &["kind"],
+                    )?,
+                    metrics_registry,
+                )?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The kind of data of a cache lookup, used as the label of the metrics.
+#[derive(Debug, Clone, Copy)]
+enum CacheKind {
+    Header,
+    Block,
+    BlockReceipts,
+    Transaction,
+    Receipt,
+}
+
+impl CacheKind {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Header => "header",
+            Self::Block => "block",
+            Self::BlockReceipts => "block_receipts",
+            Self::Transaction => "transaction",
+            Self::Receipt => "receipt",
+        }
+    }
+}
+
+/// The cached data, only containing data of blocks which have a hash, i.e. which aren't pending.
+struct Caches {
+    /// Hashes of the blocks, by number.
+    hashes: LruMap<u64, B256>,
+    /// Headers, by block hash.
+    headers: LruMap<B256, Header>,
+    /// Blocks, by block hash and whether the transactions are full.
+    blocks: LruMap<(B256, bool), ExtendedBlock>,
+    /// Receipts of the blocks with their block number, by block hash.
+    block_receipts: LruMap<B256, (u64, Vec<ExtendedTxReceipt>)>,
+    /// Transactions, by hash.
+    transactions: LruMap<B256, ExtendedTransaction>,
+    /// Receipts, by transaction hash.
+    receipts: LruMap<B256, ExtendedTxReceipt>,
+    /// Latest block number returned by the inner provider.
+    latest_block_number: Option<u64>,
+}
+
+impl Caches {
+    fn new(config: CacheConfig) -> Self {
+        Self {
+            hashes: LruMap::new(ByLength::new(config.max_blocks)),
+            headers: LruMap::new(ByLength::new(config.max_blocks)),
+            blocks: LruMap::new(ByLength::new(config.max_blocks)),
+            block_receipts: LruMap::new(ByLength::new(config.max_blocks)),
+            transactions: LruMap::new(ByLength::new(config.max_transactions)),
+            receipts: LruMap::new(ByLength::new(config.max_transactions)),
+            latest_block_number: None,
+        }
+    }
+
+    /// Returns the hash of the block, if the block id designates a block which hash is known. The
+    /// hashes of the blocks less than [`CACHED_HASH_DEPTH`] blocks below the latest block aren't
+    /// served by number, as these blocks can be replaced at the same height.
+    fn block_hash(&mut self, block_id: &BlockId) -> Option<B256> {
+        match block_id {
+            BlockId::Hash(hash) => Some(hash.block_hash),
+            BlockId::Number(BlockNumberOrTag::Number(number)) => {
+                let latest = self.latest_block_number?;
+                if number.saturating_add(CACHED_HASH_DEPTH) > latest {
+                    return None;
+                }
+                self.hashes.get(number).copied()
+            }
+            BlockId::Number(_) => None,
+        }
+    }
+
+    /// Records the hash of the block at the given number. A different hash than the recorded one
+    /// means the chain was reorganized, so all the data from this block is dropped.
+    fn insert_hash(&mut self, number: u64, hash: B256) {
+        if self.hashes.peek(&number).is_some_and(|cached| *cached != hash) {
+            self.invalidate_from(number);
+        }
+        self.hashes.insert(number, hash);
+    }
+
+    /// Records the latest block number, dropping the data of the blocks above it if the chain
+    /// head moved backwards.
+    fn update_latest_block_number(&mut self, latest: u64) {
+        if self.latest_block_number.is_some_and(|previous| previous > latest) {
+            self.invalidate_from(latest.saturating_add(1));
+        }
+        self.latest_block_number = Some(latest);
+    }
+
+    /// Drops the data of the blocks from the given number.
+    fn invalidate_from(&mut self, number: u64) {
+        let is_kept = |block_number: Option<u64>| block_number.is_some_and(|block_number| block_number < number);
+        self.hashes.retain(|block_number, _| *block_number < number);
+        self.headers.retain(|_, header| header.number < number);
+        self.blocks.retain(|_, block| block.header.number < number);
+        self.block_receipts.retain(|_, (block_number, _)| *block_number < number);
+        self.transactions.retain(|_, transaction| is_kept(transaction.block_number));
+        self.receipts.retain(|_, receipt| is_kept(receipt.block_number));
+    }
+
+    fn insert_block(&mut self, block: &ExtendedBlock, full: bool) {
+        let header = &block.header;
+        self.insert_hash(header.number, header.hash);
+        self.headers.insert(header.hash, header.clone());
+        self.blocks.insert((header.hash, full), block.clone());
+    }
+}
+
+/// Returns true if the block hash is set and not zero. Pending blocks have a zero hash and are
+/// replaced once accepted, so their data can't be cached.
+fn is_final(block_hash: Option<B256>) -> bool {
+    block_hash.is_some_and(|hash| !hash.is_zero())
+}
+
+/// Returns true if the block id designates the same block whatever the head of the chain.
+const fn is_fixed(block_id: &BlockId) -> bool {
+    matches!(block_id, BlockId::Hash(_) | BlockId::Number(BlockNumberOrTag::Number(_)))
+}
+
+/// Ethereum provider caching the headers, blocks, transactions and receipts of the inner provider
+/// in size bounded LRU caches.
+///
+/// Only the data of blocks which have a hash is cached, the data of the pending block is always
+/// fetched from the inner provider. The data of the blocks above the latest block number is
+/// dropped when the latest block number decreases, and the data of the blocks from a given number
+/// when a different block hash is seen at this number. The blocks close to the head are always
+/// fetched from the inner provider when requested by number, so that a block replaced at the same
+/// height is seen.
+#[derive(Clone)]
+pub struct CachedEthProvider<P> {
+    inner: P,
+    caches: Arc<Mutex<Caches>>,
+    metrics: Option<CacheMetrics>,
+}
+
+impl<P: std::fmt::Debug> std::fmt::Debug for CachedEthProvider<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedEthProvider").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<P> CachedEthProvider<P> {
+    pub fn new(inner: P, config: CacheConfig, metrics: Option<CacheMetrics>) -> Self {
+        Self { inner, caches: Arc::new(Mutex::new(Caches::new(config))), metrics }
+    }
+
+    /// Returns a reference to the inner provider.
+    pub const fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Drops the cached data of the blocks from the given number.
+    pub fn invalidate_from(&self, number: u64) {
+        self.caches().invalidate_from(number);
+    }
+
+    fn caches(&self) -> MutexGuard<'_, Caches> {
+        self.caches.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Looks up the cache and records the hit or miss.
+    fn lookup<T>(&self, kind: CacheKind, lookup: impl FnOnce(&mut Caches) -> Option<T>) -> Option<T> {
+        let value = lookup(&mut self.caches());
+        if let Some(metrics) = &self.metrics {
+            let counter = if value.is_some() { &metrics.hits } else { &metrics.misses };
+            counter.with_label_values(&[kind.label()]).inc();
+        }
+        value
+    }
+}
+
+impl<P> Deref for CachedEthProvider<P> {
+    type Target = P;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> BlockProvider for CachedEthProvider<P> {
+    async fn header(&self, block_id: &BlockId) -> EthApiResult<Option<Header>> {
+        if !is_fixed(block_id) {
+            return self.inner.header(block_id).await;
+        }
+        let cached = self.lookup(CacheKind::Header, |caches| {
+            let hash = caches.block_hash(block_id)?;
+            caches.headers.get(&hash).cloned()
+        });
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let header = self.inner.header(block_id).await?;
+        if let Some(header) = header.as_ref().filter(|header| is_final(Some(header.hash))) {
+            let mut caches = self.caches();
+            caches.insert_hash(header.number, header.hash);
+            caches.headers.insert(header.hash, header.clone());
+        }
+        Ok(header)
+    }
+
+    async fn block_number(&self) -> EthApiResult<U64> {
+        let block_number = self.inner.block_number().await?;
+        self.caches().update_latest_block_number(block_number.to());
+        Ok(block_number)
+    }
+
+    async fn block_by_hash(&self, hash: B256, full: bool) -> EthApiResult<Option<ExtendedBlock>> {
+        let cached = self.lookup(CacheKind::Block, |caches| caches.blocks.get(&(hash, full)).cloned());
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let block = self.inner.block_by_hash(hash, full).await?;
+        if let Some(block) = block.as_ref().filter(|block| is_final(Some(block.header.hash))) {
+            self.caches().insert_block(block, full);
+        }
+        Ok(block)
+    }
+
+    async fn block_by_number(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+        full: bool,
+    ) -> EthApiResult<Option<ExtendedBlock>> {
+        if !number_or_tag.is_number() {
+            return self.inner.block_by_number(number_or_tag, full).await;
+        }
+        let cached = self.lookup(CacheKind::Block, |caches| {
+            let hash = caches.block_hash(&number_or_tag.into())?;
+            caches.blocks.get(&(hash, full)).cloned()
+        });
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let block = self.inner.block_by_number(number_or_tag, full).await?;
+        if let Some(block) = block.as_ref().filter(|block| is_final(Some(block.header.hash))) {
+            self.caches().insert_block(block, full);
+        }
+        Ok(block)
+    }
+
+    async fn block_transaction_count_by_hash(&self, hash: B256) -> EthApiResult<Option<U256>> {
+        self.inner.block_transaction_count_by_hash(hash).await
+    }
+
+    async fn block_transaction_count_by_number(&self, number_or_tag: BlockNumberOrTag) -> EthApiResult<Option<U256>> {
+        self.inner.block_transaction_count_by_number(number_or_tag).await
+    }
+
+    async fn block_transactions(&self, block_id: Option<BlockId>) -> EthApiResult<Option<Vec<ExtendedTransaction>>> {
+        self.inner.block_transactions(block_id).await
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> ChainProvider for CachedEthProvider<P> {
+    async fn syncing(&self) -> EthApiResult<SyncStatus> {
+        self.inner.syncing().await
+    }
+
+    async fn chain_id(&self) -> EthApiResult<Option<U64>> {
+        self.inner.chain_id().await
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> TransactionProvider for CachedEthProvider<P> {
+    async fn transaction_by_hash(&self, hash: B256) -> EthApiResult<Option<ExtendedTransaction>> {
+        let cached = self.lookup(CacheKind::Transaction, |caches| caches.transactions.get(&hash).cloned());
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let transaction = self.inner.transaction_by_hash(hash).await?;
+        if let Some(transaction) = transaction.as_ref().filter(|transaction| is_final(transaction.block_hash)) {
+            let mut caches = self.caches();
+            if let (Some(number), Some(block_hash)) = (transaction.block_number, transaction.block_hash) {
+                caches.insert_hash(number, block_hash);
+            }
+            caches.transactions.insert(hash, transaction.clone());
+        }
+        Ok(transaction)
+    }
+
+    async fn transaction_by_block_hash_and_index(
+        &self,
+        hash: B256,
+        index: Index,
+    ) -> EthApiResult<Option<ExtendedTransaction>> {
+        self.inner.transaction_by_block_hash_and_index(hash, index).await
+    }
+
+    async fn transaction_by_block_number_and_index(
+        &self,
+        number_or_tag: BlockNumberOrTag,
+        index: Index,
+    ) -> EthApiResult<Option<ExtendedTransaction>> {
+        self.inner.transaction_by_block_number_and_index(number_or_tag, index).await
+    }
+
+    async fn transaction_count(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<U256> {
+        self.inner.transaction_count(address, block_id).await
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> ReceiptProvider for CachedEthProvider<P> {
+    async fn transaction_receipt(&self, hash: B256) -> EthApiResult<Option<ExtendedTxReceipt>> {
+        let cached = self.lookup(CacheKind::Receipt, |caches| caches.receipts.get(&hash).cloned());
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let receipt = self.inner.transaction_receipt(hash).await?;
+        if let Some(receipt) = receipt.as_ref().filter(|receipt| is_final(receipt.block_hash)) {
+            let mut caches = self.caches();
+            if let (Some(number), Some(block_hash)) = (receipt.block_number, receipt.block_hash) {
+                caches.insert_hash(number, block_hash);
+            }
+            caches.receipts.insert(hash, receipt.clone());
+        }
+        Ok(receipt)
+    }
+
+    async fn block_receipts(&self, block_id: Option<BlockId>) -> EthApiResult<Option<Vec<ExtendedTxReceipt>>> {
+        let Some(block_id) = block_id.filter(is_fixed) else {
+            return self.inner.block_receipts(block_id).await;
+        };
+        let cached = self.lookup(CacheKind::BlockReceipts, |caches| {
+            let hash = caches.block_hash(&block_id)?;
+            caches.block_receipts.get(&hash).map(|(_, receipts)| receipts.clone())
+        });
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let receipts = self.inner.block_receipts(Some(block_id)).await?;
+        // The block of the receipts is only known if the block has at least one transaction.
+        let block = receipts
+            .as_ref()
+            .and_then(|receipts| receipts.first())
+            .and_then(|receipt| receipt.block_number.zip(receipt.block_hash).filter(|(_, hash)| is_final(Some(*hash))));
+        if let (Some((number, block_hash)), Some(receipts)) = (block, &receipts) {
+            let mut caches = self.caches();
+            caches.insert_hash(number, block_hash);
+            caches.block_receipts.insert(block_hash, (number, receipts.clone()));
+        }
+        Ok(receipts)
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> StateProvider for CachedEthProvider<P> {
+    async fn balance(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<U256> {
+        self.inner.balance(address, block_id).await
+    }
+
+    async fn storage_at(
+        &self,
+        address: Address,
+        index: JsonStorageKey,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<B256> {
+        self.inner.storage_at(address, index, block_id).await
+    }
+
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<Bytes> {
+        self.inner.get_code(address, block_id).await
+    }
+
+    async fn call(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> EthApiResult<Bytes> {
+        self.inner.call(request, block_id, state_overrides, block_overrides).await
+    }
+
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<Vec<SimulatedBlock<ExtendedBlock>>> {
+        self.inner.simulate_v1(payload, block_id).await
+    }
+
+    async fn call_many(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<Vec<EthCallResponse>> {
+        self.inner.call_many(bundle, state_context, state_overrides).await
+    }
+
+    async fn call_bundle(&self, bundle: CallBundle) -> EthApiResult<CallBundleResponse> {
+        self.inner.call_bundle(bundle).await
+    }
+
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<B256>,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<WithOtherFields<EIP1186AccountProofResponse>> {
+        self.inner.get_proof(address, keys, block_id).await
+    }
+
+    async fn create_access_list(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<AccessListResult> {
+        self.inner.create_access_list(request, block_id).await
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> GasProvider for CachedEthProvider<P> {
+    async fn estimate_gas(
+        &self,
+        call: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<U256> {
+        self.inner.estimate_gas(call, block_id, state_overrides).await
+    }
+
+    async fn fee_history(
+        &self,
+        block_count: U64,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> EthApiResult<FeeHistory> {
+        self.inner.fee_history(block_count, newest_block, reward_percentiles).await
+    }
+
+    async fn gas_price(&self) -> EthApiResult<U256> {
+        self.inner.gas_price().await
+    }
+}
+
+#[async_trait]
+impl<P: EthereumProvider + Send + Sync> LogProvider for CachedEthProvider<P> {
+    async fn get_logs(&self, filter: Filter) -> EthApiResult<FilterChanges> {
+        self.inner.get_logs(filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mock_provider::MockEthereumProviderStruct;
+    use alloy_rpc_types::Block;
+
+    fn block(number: u64, hash: B256) -> ExtendedBlock {
+        WithOtherFields::new(Block { header: Header { hash, number, ..Default::default() }, ..Default::default() })
+    }
+
+    #[tokio::test]
+    async fn test_cached_block_by_hash_and_number() {
+        // Given
+        let mut mock_provider = MockEthereumProviderStruct::new();
+        mock_provider.expect_block_number().times(1).returning(|| Ok(U64::from(1 + CACHED_HASH_DEPTH)));
+        mock_provider.expect_block_by_hash().times(1).returning(|hash, _| Ok(Some(block(1, hash))));
+        let provider = CachedEthProvider::new(mock_provider, CacheConfig::DEFAULT, None);
+        let hash = B256::repeat_byte(1);
+        provider.block_number().await.unwrap();
+
+        // When
+        let by_hash = provider.block_by_hash(hash, true).await.unwrap();
+        let cached_by_hash = provider.block_by_hash(hash, true).await.unwrap();
+        let cached_by_number = provider.block_by_number(BlockNumberOrTag::Number(1), true).await.unwrap();
+        let cached_header = provider.header(&BlockId::Number(BlockNumberOrTag::Number(1))).await.unwrap();
+
+        // Then
+        assert_eq!(by_hash, cached_by_hash);
+        assert_eq!(by_hash, cached_by_number);
+        assert_eq!(cached_header.map(|header| header.hash), Some(hash));
+    }
+
+    #[tokio::test]
+    async fn test_pending_block_is_not_cached() {
+        // Given
+        let mut mock_provider = MockEthereumProviderStruct::new();
+        mock_provider.expect_block_by_number().times(2).returning(|_, _| Ok(Some(block(2, B256::ZERO))));
+        let provider = CachedEthProvider::new(mock_provider, CacheConfig::DEFAULT, None);
+
+        // When
+        provider.block_by_number(BlockNumberOrTag::Number(2), false).await.unwrap();
+        let block = provider.block_by_number(BlockNumberOrTag::Number(2), false).await.unwrap();
+
+        // Then
+        assert_eq!(block.map(|block| block.header.hash), Some(B256::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_reorg_invalidates_blocks() {
+        // Given
+        let mut mock_provider = MockEthereumProviderStruct::new();
+        let mut latest = [5u64, 3].into_iter();
+        mock_provider.expect_block_number().times(2).returning(move || Ok(U64::from(latest.next().unwrap())));
+        mock_provider.expect_block_by_hash().times(2).returning(|hash, _| Ok(Some(block(5, hash))));
+        let provider = CachedEthProvider::new(mock_provider, CacheConfig::DEFAULT, None);
+        let hash = B256::repeat_byte(5);
+
+        // When
+        provider.block_number().await.unwrap();
+        provider.block_by_hash(hash, false).await.unwrap();
+        // The chain head moves back below the cached block.
+        provider.block_number().await.unwrap();
+
+        // Then
+        provider.block_by_hash(hash, false).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_block_replaced_at_same_height() {
+        // Given
+        let mut mock_provider = MockEthereumProviderStruct::new();
+        let mut hashes = [B256::repeat_byte(1), B256::repeat_byte(2)].into_iter();
+        mock_provider.expect_block_number().times(1).returning(|| Ok(U64::from(5)));
+        mock_provider
+            .expect_block_by_number()
+            .times(2)
+            .returning(move |_, _| Ok(Some(block(5, hashes.next().unwrap()))));
+        let provider = CachedEthProvider::new(mock_provider, CacheConfig::DEFAULT, None);
+        provider.block_number().await.unwrap();
+
+        // When
+        let replaced = provider.block_by_number(BlockNumberOrTag::Number(5), false).await.unwrap();
+        // The block at the head is replaced by another block at the same height.
+        let block = provider.block_by_number(BlockNumberOrTag::Number(5), false).await.unwrap();
+
+        // Then
+        assert_eq!(replaced.map(|block| block.header.hash), Some(B256::repeat_byte(1)));
+        assert_eq!(block.map(|block| block.header.hash), Some(B256::repeat_byte(2)));
+        assert!(provider.caches().blocks.peek(&(B256::repeat_byte(1), false)).is_none());
+    }
+}
@@ -13,7 +13,6 @@ use alloy_rpc_types_trace::geth::{GethDebugTracingCallOptions, GethDebugTracingO
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use reth_primitives::{Block, Header, Log, Receipt, ReceiptWithBloom, TransactionSigned};
-use std::sync::Arc;
 
 #[async_trait]
 #[auto_impl(Arc, &)]
@@ -58,7 +57,7 @@ impl<P: EthereumProvider> DebugDataProvider<P> {
 }
 
 #[async_trait]
-impl<P: EthereumProvider + Send + Sync + 'static> DebugProvider for DebugDataProvider<P> {
+impl<P: EthereumProvider + Clone + Send + Sync + 'static> DebugProvider for DebugDataProvider<P> {
     async fn raw_header(&self, block_id: BlockId) -> EthApiResult<Bytes> {
         let mut res = Vec::new();
         if let Some(header) = self.eth_provider.header(&block_id).await?.map(Header::try_from).transpose()? {
@@ -171,8 +170,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> DebugProvider for DebugDataPro
         block_number: BlockNumberOrTag,
         opts: Option<GethDebugTracingOptions>,
     ) -> EthApiResult<Vec<TraceResult>> {
-        let provider = Arc::new(&self.eth_provider);
-        let tracer = TracerBuilder::new(provider)
+        let tracer = TracerBuilder::new(self.eth_provider.clone())
             .await?
             .with_block_id(block_number.into())
             .await?
@@ -187,7 +185,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> DebugProvider for DebugDataPro
         block_hash: B256,
         opts: Option<GethDebugTracingOptions>,
     ) -> EthApiResult<Vec<TraceResult>> {
-        let tracer = TracerBuilder::new(Arc::new(&self.eth_provider))
+        let tracer = TracerBuilder::new(self.eth_provider.clone())
             .await?
             .with_block_id(block_hash.into())
             .await?
@@ -202,7 +200,7 @@ impl<P: EthereumProvider + Send + Sync + 'static> DebugProvider for DebugDataPro
         transaction_hash: B256,
         opts: Option<GethDebugTracingOptions>,
     ) -> EthApiResult<GethTrace> {
-        let tracer = TracerBuilder::new(Arc::new(&self.eth_provider))
+        let tracer = TracerBuilder::new(self.eth_provider.clone())
             .await?
             .with_transaction_hash(transaction_hash)
             .await?
@@ -218,12 +216,12 @@ impl<P: EthereumProvider + Send + Sync + 'static> DebugProvider for DebugDataPro
         block_number: Option<BlockId>,
         opts: Option<GethDebugTracingCallOptions>,
     ) -> EthApiResult<GethTrace> {
-        let tracer = TracerBuilder::new(Arc::new(&self.eth_provider))
+        let tracer = TracerBuilder::new(self.eth_provider.clone())
             .await?
             .with_block_id(block_number.unwrap_or_default())
             .await?
             .with_tracing_options(opts.unwrap_or_default().into())
-            .build()?;
+            .build_for_call();
 
         Ok(tracer.debug_transaction_request(&request)?)
     }
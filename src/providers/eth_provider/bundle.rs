@@ -0,0 +1,285 @@
+use super::{
+    constant::CALL_REQUEST_GAS_LIMIT,
+    database::state::{EthCacheDatabase, EthDatabase},
+    error::{EthApiError, EvmError, ExecutionError, SignatureError, TransactionError},
+    provider::{EthApiResult, EthDataProvider, EthereumProvider},
+    state::call_env_at,
+    BlockProvider, ChainProvider,
+};
+use crate::models::bundle::{CallBundle, CallBundleResponse, CallBundleTransactionResult};
+use alloy_eips::BlockId;
+use alloy_primitives::{keccak256, Address, U256, U64};
+use alloy_rlp::Decodable;
+use alloy_rpc_types::{state::StateOverride, Bundle, EthCallResponse, StateContext, TransactionRequest};
+use reth_evm_ethereum::EthEvmConfig;
+use reth_node_api::{ConfigureEvm, ConfigureEvmEnv};
+use reth_primitives::TransactionSigned;
+use reth_revm::{
+    db::CacheDB,
+    primitives::{ExecutionResult, ResultAndState, TxEnv},
+    DatabaseCommit, DatabaseRef,
+};
+use reth_rpc_eth_types::{
+    revm_utils::{apply_block_overrides, apply_state_overrides},
+    EthApiError as RethEthApiError,
+};
+use std::sync::Arc;
+
+/// Maximum number of transactions executed by a single bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 256;
+
+impl<SP> EthDataProvider<SP>
+where
+    SP: starknet::providers::Provider + Send + Sync,
+{
+    /// Executes the transaction requests of the bundle one after the other on top of the state
+    /// context, and returns the output or the error of each of them. The gas of all the requests
+    /// is capped, as the gas of a single call.
+    pub(crate) async fn execute_bundle(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<Vec<EthCallResponse>> {
+        let Bundle { transactions, block_override } = bundle;
+        if transactions.is_empty() {
+            return Err(RethEthApiError::InvalidParams("bundle missing transactions".to_string()).into());
+        }
+        check_bundle_length(transactions.len())?;
+
+        let StateContext { block_number, transaction_index } = state_context.unwrap_or_default();
+        let block_id = block_number.unwrap_or_default();
+        let header = self.call_header(block_id).await?;
+        let chain_id = self.chain_id().await?.unwrap_or_default().to();
+        let evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+
+        // Executing the bundle in the middle of the block requires to replay the transactions of
+        // the block preceding the index on top of the state of the parent block.
+        let mut db = match transaction_index.and_then(|index| index.index()) {
+            Some(index) => {
+                let Some(parent_number) = header.number.checked_sub(1) else {
+                    return Err(RethEthApiError::InvalidParams(
+                        "cannot execute a bundle in the middle of the genesis block".to_string(),
+                    )
+                    .into());
+                };
+                let transactions = self.block_transactions(Some(block_id)).await?.unwrap_or_default();
+                let mut db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, parent_number.into())));
+                let mut env = call_env_at(&header, &TransactionRequest::default(), chain_id);
+                env.block.basefee = U256::from(header.base_fee_per_gas.unwrap_or_default());
+
+                // The transactions reverted on Starknet didn't change the state.
+                for tx in transactions.iter().take(index).filter(|tx| tx.other.get("reverted").is_none()) {
+                    env.tx = evm_config.tx_env(&tx.clone().try_into()?, tx.from);
+                    evm_config
+                        .evm_with_env(&mut db.0, env.clone())
+                        .transact_commit()
+                        .map_err(|err| TransactionError::Call(err.into()))?;
+                }
+                db
+            }
+            None => EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id))),
+        };
+
+        if let Some(state_overrides) = state_overrides {
+            apply_state_overrides(state_overrides, &mut db.0)?;
+        }
+
+        let mut remaining_gas = CALL_REQUEST_GAS_LIMIT;
+        let mut responses = Vec::with_capacity(transactions.len());
+        for request in transactions {
+            if remaining_gas == 0 {
+                return Err(gas_cap_exceeded());
+            }
+            let mut env = call_env_at(&header, &request, chain_id);
+            if let Some(block_override) = block_override.clone() {
+                apply_block_overrides(block_override, &mut db.0, &mut env.block);
+                // Requests without gas limit use the gas limit of the overridden block.
+                if request.gas.is_none() {
+                    env.tx.gas_limit = env.block.gas_limit.saturating_to();
+                }
+            }
+            env.tx.gas_limit = env.tx.gas_limit.min(remaining_gas);
+
+            let result = evm_config
+                .evm_with_env(&mut db.0, env)
+                .transact_commit()
+                .map_err(|err| TransactionError::Call(err.into()))?;
+            remaining_gas = remaining_gas.saturating_sub(result.gas_used());
+
+            responses.push(match result {
+                ExecutionResult::Success { output, .. } => {
+                    EthCallResponse { value: Some(output.into_data()), error: None }
+                }
+                ExecutionResult::Revert { output, .. } => EthCallResponse {
+                    value: None,
+                    error: Some(ExecutionError::from(EvmError::Other(output)).to_string()),
+                },
+                ExecutionResult::Halt { reason, .. } => {
+                    EthCallResponse { value: None, error: Some(format!("execution halted: {reason:?}")) }
+                }
+            });
+        }
+
+        Ok(responses)
+    }
+
+    /// Executes the signed transactions of the bundle one after the other on top of the state
+    /// block, and returns the result of each of them and the payments to the coinbase. The gas
+    /// limits of all the transactions are capped, as the gas of a single call.
+    pub(crate) async fn execute_signed_bundle(&self, bundle: CallBundle) -> EthApiResult<CallBundleResponse> {
+        let CallBundle { txs, block_number, state_block_number, coinbase, timestamp, gas_limit, base_fee } = bundle;
+        if txs.is_empty() {
+            return Err(RethEthApiError::InvalidParams("bundle missing txs".to_string()).into());
+        }
+        check_bundle_length(txs.len())?;
+
+        let transactions = txs
+            .iter()
+            .map(|tx| {
+                let transaction = TransactionSigned::decode(&mut tx.as_ref())?;
+                let signer = transaction.recover_signer().ok_or(SignatureError::Recovery)?;
+                Ok((transaction, signer))
+            })
+            .collect::<EthApiResult<Vec<_>>>()?;
+        let total_gas_limit =
+            transactions.iter().fold(0u64, |total, (transaction, _)| total.saturating_add(transaction.gas_limit()));
+        if total_gas_limit > CALL_REQUEST_GAS_LIMIT {
+            return Err(gas_cap_exceeded());
+        }
+
+        let block_id = BlockId::Number(state_block_number);
+        let header = self.call_header(block_id).await?;
+        let chain_id = self.chain_id().await?.unwrap_or_default().to();
+
+        let mut env = call_env_at(&header, &TransactionRequest::default(), chain_id);
+        env.block.number = U256::from(block_number);
+        env.block.basefee = base_fee.unwrap_or_else(|| U256::from(header.base_fee_per_gas.unwrap_or_default()));
+        if let Some(coinbase) = coinbase {
+            env.block.coinbase = coinbase;
+        }
+        if let Some(timestamp) = timestamp {
+            env.block.timestamp = U256::from(timestamp);
+        }
+        if let Some(gas_limit) = gas_limit {
+            env.block.gas_limit = U256::from(gas_limit);
+        }
+
+        let coinbase = env.block.coinbase;
+        let mut db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id)));
+        let initial_coinbase_balance = balance(&db, coinbase)?;
+        let evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut total_gas_used = 0;
+        let mut total_gas_fees = U256::ZERO;
+        let mut hashes = Vec::with_capacity(transactions.len() * 32);
+        for (transaction, signer) in transactions {
+            let coinbase_balance_before_tx = balance(&db, coinbase)?;
+            env.tx = evm_config.tx_env(&transaction, signer);
+            let gas_price = priority_fee_per_gas(&env.tx, env.block.basefee);
+            let to_address = env.tx.transact_to.to().copied();
+
+            let ResultAndState { result, state } = evm_config
+                .evm_with_env(&mut db.0, env.clone())
+                .transact()
+                .map_err(|err| TransactionError::Call(err.into()))?;
+            db.0.commit(state);
+
+            let gas_used = result.gas_used();
+            let gas_fees = U256::from(gas_used) * gas_price;
+            total_gas_used += gas_used;
+            total_gas_fees += gas_fees;
+
+            let coinbase_diff = balance(&db, coinbase)?.saturating_sub(coinbase_balance_before_tx);
+            let (value, revert) = match result {
+                ExecutionResult::Success { output, .. } => (Some(output.into_data()), None),
+                ExecutionResult::Revert { output, .. } => {
+                    (None, Some(ExecutionError::from(EvmError::Other(output)).to_string()))
+                }
+                ExecutionResult::Halt { reason, .. } => (None, Some(format!("execution halted: {reason:?}"))),
+            };
+
+            let tx_hash = transaction.hash();
+            hashes.extend_from_slice(tx_hash.as_slice());
+            results.push(CallBundleTransactionResult {
+                tx_hash,
+                from_address: signer,
+                to_address,
+                gas_used: U64::from(gas_used),
+                gas_price,
+                gas_fees,
+                coinbase_diff,
+                eth_sent_to_coinbase: coinbase_diff.saturating_sub(gas_fees),
+                value,
+                revert,
+            });
+        }
+
+        let coinbase_diff = balance(&db, coinbase)?.saturating_sub(initial_coinbase_balance);
+        Ok(CallBundleResponse {
+            bundle_hash: keccak256(hashes),
+            bundle_gas_price: coinbase_diff.checked_div(U256::from(total_gas_used)).unwrap_or_default(),
+            coinbase_diff,
+            eth_sent_to_coinbase: coinbase_diff.saturating_sub(total_gas_fees),
+            gas_fees: total_gas_fees,
+            results,
+            state_block_number: U64::from(header.number),
+            total_gas_used: U64::from(total_gas_used),
+        })
+    }
+}
+
+/// Checks that the bundle doesn't exceed the maximum number of transactions.
+fn check_bundle_length(len: usize) -> EthApiResult<()> {
+    if len > MAX_BUNDLE_TRANSACTIONS {
+        return Err(RethEthApiError::InvalidParams(format!(
+            "too many transactions, the maximum is {MAX_BUNDLE_TRANSACTIONS}"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns the error of the bundles which gas exceeds the cap.
+fn gas_cap_exceeded() -> EthApiError {
+    RethEthApiError::InvalidParams(format!("the bundle exceeds the gas cap of {CALL_REQUEST_GAS_LIMIT}")).into()
+}
+
+/// Returns the balance of the address in the database.
+fn balance<P: EthereumProvider + Send + Sync>(db: &EthCacheDatabase<P>, address: Address) -> EthApiResult<U256> {
+    Ok(db.0.basic_ref(address)?.map(|account| account.balance).unwrap_or_default())
+}
+
+/// Returns the fee per gas paid to the coinbase by the transaction, on top of the base fee.
+fn priority_fee_per_gas(tx: &TxEnv, base_fee: U256) -> U256 {
+    let effective_gas_price = match tx.gas_priority_fee {
+        Some(priority_fee) => tx.gas_price.min(base_fee.saturating_add(priority_fee)),
+        None => tx.gas_price,
+    };
+    effective_gas_price.saturating_sub(base_fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_fee_per_gas() {
+        // Given
+        let base_fee = U256::from(10);
+        let legacy = TxEnv { gas_price: U256::from(15), ..Default::default() };
+        let capped = TxEnv { gas_price: U256::from(15), gas_priority_fee: Some(U256::from(8)), ..Default::default() };
+        let tipped = TxEnv { gas_price: U256::from(30), gas_priority_fee: Some(U256::from(2)), ..Default::default() };
+
+        // When
+        let legacy = priority_fee_per_gas(&legacy, base_fee);
+        let capped = priority_fee_per_gas(&capped, base_fee);
+        let tipped = priority_fee_per_gas(&tipped, base_fee);
+
+        // Then
+        assert_eq!(legacy, U256::from(5));
+        assert_eq!(capped, U256::from(5));
+        assert_eq!(tipped, U256::from(2));
+    }
+}
@@ -1,14 +1,20 @@
+use crate::config::Config;
 use alloy_primitives::{B256, U256};
 use serde::{Deserialize, Serialize};
 use starknet::core::types::Felt;
-use std::{str::FromStr, sync::LazyLock};
+use std::sync::LazyLock;
 
 /// Maximum priority fee per gas
 pub static MAX_PRIORITY_FEE_PER_GAS: LazyLock<u64> = LazyLock::new(|| 0);
 
 /// Maximum number of logs that can be fetched in a single request
 pub static MAX_LOGS: LazyLock<Option<u64>> =
-    LazyLock::new(|| std::env::var("MAX_LOGS").ok().and_then(|val| u64::from_str(&val).ok()));
+    LazyLock::new(|| Config::current().expect("failed to read the configuration").max_logs);
+
+/// Maximum number of blocks that can be queried in a single logs request
+pub static MAX_LOGS_BLOCK_RANGE: LazyLock<Option<u64>> = LazyLock::new(|| {
+    Config::current().expect("failed to read the configuration").max_logs_block_range.filter(|range| *range > 0)
+});
 
 /// Gas limit for estimate gas and call
 pub const CALL_REQUEST_GAS_LIMIT: u64 = 50_000_000;
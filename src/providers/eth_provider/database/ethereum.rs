@@ -19,6 +19,8 @@ use alloy_serde::WithOtherFields;
 use async_trait::async_trait;
 use mongodb::bson::doc;
 use reth_primitives::BlockBody;
+use starknet::core::types::Felt;
+use std::collections::HashMap;
 use tracing::instrument;
 
 /// Trait for interacting with a database that stores Ethereum typed
@@ -37,6 +39,13 @@ pub trait EthereumTransactionStore {
     async fn upsert_transaction(&self, transaction: ExtendedTransaction) -> Result<(), EthApiError>;
     /// Upserts the given transaction hash mapping (Ethereum -> Starknet).
     async fn upsert_transaction_hashes(&self, transaction_hashes: EthStarknetHashes) -> Result<(), EthApiError>;
+    /// Returns the hash of the Starknet transaction which relayed the Ethereum transaction.
+    async fn starknet_transaction_hash(&self, eth_hash: &B256) -> Result<Option<Felt>, EthApiError>;
+    /// Returns the hashes of the Starknet transactions which relayed the Ethereum transactions, by
+    /// Ethereum transaction hash. Transactions which weren't relayed by the node are missing.
+    async fn starknet_transaction_hashes(&self, eth_hashes: &[B256]) -> Result<HashMap<B256, Felt>, EthApiError>;
+    /// Returns the hash of the Ethereum transaction relayed by the Starknet transaction.
+    async fn eth_transaction_hash(&self, starknet_hash: &Felt) -> Result<Option<B256>, EthApiError>;
 }
 
 #[async_trait]
@@ -72,6 +81,35 @@ impl EthereumTransactionStore for Database {
             .build();
         Ok(self.update_one(StoredEthStarknetTransactionHash::from(transaction_hashes), filter, true).await?)
     }
+
+    #[instrument(skip_all, name = "db::starknet_transaction_hash", err)]
+    async fn starknet_transaction_hash(&self, eth_hash: &B256) -> Result<Option<Felt>, EthApiError> {
+        let filter =
+            EthDatabaseFilterBuilder::<filter::EthStarknetTransactionHash>::default().with_tx_hash(eth_hash).build();
+        let mapping = self.get_one::<StoredEthStarknetTransactionHash>(filter, None).await?;
+        Ok(mapping.map(|mapping| mapping.hashes.starknet_hash))
+    }
+
+    #[instrument(skip_all, name = "db::starknet_transaction_hashes", err)]
+    async fn starknet_transaction_hashes(&self, eth_hashes: &[B256]) -> Result<HashMap<B256, Felt>, EthApiError> {
+        if eth_hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let filter = EthDatabaseFilterBuilder::<filter::EthStarknetTransactionHash>::default()
+            .with_tx_hashes(eth_hashes)
+            .build();
+        let mappings = self.get::<StoredEthStarknetTransactionHash>(filter, None).await?;
+        Ok(mappings.into_iter().map(|mapping| (mapping.hashes.eth_hash, mapping.hashes.starknet_hash)).collect())
+    }
+
+    #[instrument(skip_all, name = "db::eth_transaction_hash", err)]
+    async fn eth_transaction_hash(&self, starknet_hash: &Felt) -> Result<Option<B256>, EthApiError> {
+        let filter = EthDatabaseFilterBuilder::<filter::EthStarknetTransactionHash>::default()
+            .with_starknet_tx_hash(starknet_hash)
+            .build();
+        let mapping = self.get_one::<StoredEthStarknetTransactionHash>(filter, None).await?;
+        Ok(mapping.map(|mapping| mapping.hashes.eth_hash))
+    }
 }
 
 /// Trait for interacting with a database that stores Ethereum typed
@@ -83,6 +121,8 @@ pub trait EthereumBlockStore {
     /// Returns the header for the given hash or number. Returns None if the
     /// header is not found.
     async fn header(&self, block_hash_or_number: BlockHashOrNumber) -> Result<Option<Header>, EthApiError>;
+    /// Returns the headers in the block number range `[from, to]`, ordered by block number.
+    async fn headers(&self, from: u64, to: u64) -> Result<Vec<Header>, EthApiError>;
     /// Returns the block for the given hash or number. Returns None if the
     /// block is not found.
     async fn block(
@@ -122,6 +162,14 @@ impl EthereumBlockStore for Database {
             .map(Into::into))
     }
 
+    #[instrument(skip_all, name = "db::headers", err)]
+    async fn headers(&self, from: u64, to: u64) -> Result<Vec<Header>, EthApiError> {
+        let filter = EthDatabaseFilterBuilder::<filter::Header>::default().with_block_number_range(from, to).build();
+        let mut headers: Vec<Header> = self.get_and_map_to::<_, StoredHeader>(filter, None).await?;
+        headers.sort_unstable_by_key(|header| header.number);
+        Ok(headers)
+    }
+
     #[instrument(skip_all, name = "db::block", err)]
     async fn block(
         &self,
@@ -191,7 +239,6 @@ mod tests {
     use crate::test_utils::mongo::{MongoFuzzer, RANDOM_BYTES_SIZE};
     use arbitrary::Arbitrary;
     use rand::{self, Rng};
-    use starknet::core::types::Felt;
 
     #[tokio::test(flavor = "multi_thread")]
     async fn test_ethereum_transaction_store() {
@@ -314,6 +361,12 @@ mod tests {
 
         // Test retrieving non-existing header by block number
         assert_eq!(database.header(rng.gen::<u64>().into()).await.unwrap(), None);
+
+        // Test retrieving headers by block number range
+        let headers = database.headers(0, header_block_hash.number).await.unwrap();
+        assert!(headers.contains(header_block_hash));
+        assert!(headers.windows(2).all(|w| w[0].number <= w[1].number));
+        assert!(headers.iter().all(|header| header.number <= header_block_hash.number));
     }
 
     async fn test_get_blocks(database: &Database, mongo_fuzzer: &MongoFuzzer, u: &mut arbitrary::Unstructured<'_>) {
@@ -462,4 +515,26 @@ mod tests {
             "The transaction hash mapping was not updated correctly"
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transaction_hashes_lookup() {
+        // Given
+        let mut mongo_fuzzer = MongoFuzzer::new(RANDOM_BYTES_SIZE).await;
+        let database = mongo_fuzzer.mock_database(1).await;
+        let eth_hash = B256::random();
+        let starknet_hash =
+            Felt::from_hex("0x03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb").unwrap();
+        database.upsert_transaction_hashes(EthStarknetHashes { eth_hash, starknet_hash }).await.unwrap();
+
+        // When
+        let found_starknet_hash = database.starknet_transaction_hash(&eth_hash).await.unwrap();
+        let found_eth_hash = database.eth_transaction_hash(&starknet_hash).await.unwrap();
+        let found_hashes = database.starknet_transaction_hashes(&[eth_hash, B256::random()]).await.unwrap();
+
+        // Then
+        assert_eq!(found_starknet_hash, Some(starknet_hash));
+        assert_eq!(found_eth_hash, Some(eth_hash));
+        assert_eq!(found_hashes, HashMap::from([(eth_hash, starknet_hash)]));
+        assert_eq!(database.eth_transaction_hash(&Felt::ONE).await.unwrap(), None);
+    }
 }
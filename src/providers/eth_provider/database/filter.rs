@@ -5,6 +5,7 @@ use crate::providers::eth_provider::constant::{
 use alloy_primitives::{Address, B256};
 use alloy_rpc_types::{BlockHashOrNumber, Index, Topic};
 use mongodb::bson::{doc, Document};
+use starknet::core::types::Felt;
 use std::fmt::{Display, LowerHex};
 
 /// A trait that defines possible key filters for blocks in the
@@ -194,6 +195,28 @@ impl<T: BlockFiltering + Display + Default> EthDatabaseFilterBuilder<T> {
             BlockHashOrNumber::Number(number) => self.with_block_number(number),
         }
     }
+
+    /// Adds a filter on the block number range.
+    #[must_use]
+    pub fn with_block_number_range(mut self, from: u64, to: u64) -> Self {
+        let key = format!("{}.{}", self.target, self.target.block_number());
+        self.filter.insert(
+            key,
+            doc! {"$gte": format_hex(from, BLOCK_NUMBER_HEX_STRING_LEN), "$lte": format_hex(to, BLOCK_NUMBER_HEX_STRING_LEN)},
+        );
+        self
+    }
+
+    /// Adds a filter on a set of block numbers.
+    #[must_use]
+    pub fn with_block_numbers(mut self, numbers: &[u64]) -> Self {
+        let key = format!("{}.{}", self.target, self.target.block_number());
+        self.filter.insert(
+            key,
+            doc! {"$in": numbers.iter().map(|n| format_hex(n, BLOCK_NUMBER_HEX_STRING_LEN)).collect::<Vec<_>>()},
+        );
+        self
+    }
 }
 
 impl<T: TransactionFiltering + Display + Default> EthDatabaseFilterBuilder<T> {
@@ -205,6 +228,15 @@ impl<T: TransactionFiltering + Display + Default> EthDatabaseFilterBuilder<T> {
         self
     }
 
+    /// Adds a filter on a set of transaction hashes.
+    #[must_use]
+    pub fn with_tx_hashes(mut self, hashes: &[B256]) -> Self {
+        let key = format!("{}.{}", self.target, self.target.transaction_hash());
+        self.filter
+            .insert(key, doc! {"$in": hashes.iter().map(|h| format_hex(h, HASH_HEX_STRING_LEN)).collect::<Vec<_>>()});
+        self
+    }
+
     /// Adds a filter on the transaction index in the block.
     #[must_use]
     pub fn with_tx_index(mut self, index: &Index) -> Self {
@@ -215,6 +247,17 @@ impl<T: TransactionFiltering + Display + Default> EthDatabaseFilterBuilder<T> {
     }
 }
 
+impl EthDatabaseFilterBuilder<EthStarknetTransactionHash> {
+    /// Adds a filter on the Starknet transaction hash.
+    #[must_use]
+    pub fn with_starknet_tx_hash(mut self, hash: &Felt) -> Self {
+        let key = format!("{}.starknet_hash", self.target);
+        // Felts are stored as hex strings without padding.
+        self.filter.insert(key, format!("{hash:#x}"));
+        self
+    }
+}
+
 impl<T: LogFiltering + BlockFiltering + Display + Default> EthDatabaseFilterBuilder<T> {
     /// Adds a filter on the log address.
     #[must_use]
@@ -230,17 +273,6 @@ impl<T: LogFiltering + BlockFiltering + Display + Default> EthDatabaseFilterBuil
         self
     }
 
-    /// Adds a filter on the block number range.
-    #[must_use]
-    pub fn with_block_number_range(mut self, from: u64, to: u64) -> Self {
-        let key = format!("{}.{}", self.target, self.target.block_number());
-        self.filter.insert(
-            key,
-            doc! {"$gte": format_hex(from, BLOCK_NUMBER_HEX_STRING_LEN), "$lte": format_hex(to, BLOCK_NUMBER_HEX_STRING_LEN)},
-        );
-        self
-    }
-
     /// Adds a filter on the topics.
     #[must_use]
     pub fn with_topics(mut self, topics: &[Topic; 4]) -> Self {
@@ -395,6 +427,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_receipt_transaction_hashes_filter() {
+        // Given
+        let builder = EthDatabaseFilterBuilder::<Receipt>::default();
+
+        // When
+        let filter = builder.with_tx_hashes(&[B256::left_padding_from(&[1]), B256::left_padding_from(&[2])]).build();
+
+        // Then
+        assert_eq!(
+            filter,
+            doc! {"receipt.transactionHash": {"$in": [
+                "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "0x0000000000000000000000000000000000000000000000000000000000000002"
+            ]}}
+        );
+    }
+
+    #[test]
+    fn test_starknet_transaction_hash_filter() {
+        // Given
+        let builder = EthDatabaseFilterBuilder::<EthStarknetTransactionHash>::default();
+
+        // When
+        let filter = builder.with_starknet_tx_hash(&Felt::from(0x1234)).build();
+
+        // Then
+        assert_eq!(filter, doc! {"hashes.starknet_hash": "0x1234"});
+    }
+
     #[test]
     fn test_receipt_block_number_filter() {
         // Given
@@ -446,6 +508,18 @@ mod tests {
         assert_eq!(filter, doc! {"header.number": "0x0000000000000001"});
     }
 
+    #[test]
+    fn test_header_block_number_range_filter() {
+        // Given
+        let builder = EthDatabaseFilterBuilder::<Header>::default();
+
+        // When
+        let filter = builder.with_block_number_range(1, 10).build();
+
+        // Then
+        assert_eq!(filter, doc! {"header.number": {"$gte": "0x0000000000000001", "$lte": "0x000000000000000a"}});
+    }
+
     #[test]
     fn test_log_block_hash_filter() {
         // Given
@@ -473,6 +547,18 @@ mod tests {
         assert_eq!(filter, doc! {"log.blockNumber": {"$gte": "0x0000000000000001", "$lte": "0x000000000000000a"}});
     }
 
+    #[test]
+    fn test_log_block_numbers_filter() {
+        // Given
+        let builder = EthDatabaseFilterBuilder::<Log>::default();
+
+        // When
+        let filter = builder.with_block_numbers(&[1, 10]).build();
+
+        // Then
+        assert_eq!(filter, doc! {"log.blockNumber": {"$in": ["0x0000000000000001", "0x000000000000000a"]}});
+    }
+
     #[test]
     fn test_log_empty_addresses_filter() {
         // Given
@@ -0,0 +1,169 @@
+use super::{CollectionName, Database, DatabaseResult};
+use crate::providers::eth_provider::database::types::{
+    header::StoredHeader,
+    log::StoredLog,
+    receipt::StoredTransactionReceipt,
+    transaction::{StoredEthStarknetTransactionHash, StoredTransaction},
+};
+use eyre::eyre;
+use futures::TryStreamExt;
+use mongodb::{
+    bson::{Bson, Document},
+    error::{Error, ErrorKind},
+    options::IndexOptions,
+    IndexModel,
+};
+use serde::{Deserialize, Serialize};
+
+/// Code of the error returned by `MongoDB` when listing the indexes of a collection which doesn't exist.
+const NAMESPACE_NOT_FOUND_CODE: i32 = 26;
+
+/// An ascending index of a collection, required by the queries of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexSpec {
+    /// Name of the index when created by the node.
+    pub name: &'static str,
+    /// Indexed fields, in order.
+    pub keys: &'static [&'static str],
+}
+
+impl IndexSpec {
+    /// Returns the keys document of the index.
+    pub fn keys_document(&self) -> Document {
+        self.keys.iter().map(|key| ((*key).to_string(), Bson::Int32(1))).collect()
+    }
+
+    /// Returns true if the existing index covers the same fields in the same order, whatever its
+    /// name and the direction of its fields.
+    pub fn is_satisfied_by(&self, index: &IndexModel) -> bool {
+        index.keys.keys().map(String::as_str).eq(self.keys.iter().copied())
+    }
+
+    /// Returns the model used to create the index.
+    pub fn model(&self) -> IndexModel {
+        IndexModel::builder()
+            .keys(self.keys_document())
+            .options(IndexOptions::builder().name(self.name.to_string()).build())
+            .build()
+    }
+}
+
+/// Behavior of the node when the indexes required by its queries are missing at startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "binaries", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum IndexMode {
+    /// Log a warning for each missing index.
+    #[default]
+    Warn,
+    /// Create the missing indexes.
+    Create,
+    /// Refuse to start.
+    Refuse,
+}
+
+impl Database {
+    /// Checks that the indexes of all the collections exist, and depending on the mode creates the
+    /// missing ones, warns about them or returns an error.
+    pub async fn ensure_indexes(&self, mode: IndexMode) -> eyre::Result<()> {
+        let mut missing = Vec::new();
+        missing.extend(self.ensure_collection_indexes::<StoredHeader>(mode).await?);
+        missing.extend(self.ensure_collection_indexes::<StoredTransaction>(mode).await?);
+        missing.extend(self.ensure_collection_indexes::<StoredTransactionReceipt>(mode).await?);
+        missing.extend(self.ensure_collection_indexes::<StoredLog>(mode).await?);
+        missing.extend(self.ensure_collection_indexes::<StoredEthStarknetTransactionHash>(mode).await?);
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+        match mode {
+            IndexMode::Create => Ok(()),
+            IndexMode::Warn => {
+                for index in &missing {
+                    tracing::warn!(%index, "missing database index, queries relying on it will be slow");
+                }
+                Ok(())
+            }
+            IndexMode::Refuse => Err(eyre!("missing database indexes: {}", missing.join(", "))),
+        }
+    }
+
+    /// Returns the indexes of the collection `T` which are missing, after creating them if the
+    /// mode is [`IndexMode::Create`].
+    async fn ensure_collection_indexes<T>(&self, mode: IndexMode) -> DatabaseResult<Vec<String>>
+    where
+        T: CollectionName + Sync + Send,
+    {
+        let missing = self.missing_indexes::<T>().await?;
+        if mode == IndexMode::Create && !missing.is_empty() {
+            tracing::info!(collection = T::collection_name(), count = missing.len(), "creating database indexes");
+            self.collection::<T>().create_indexes(missing.iter().map(IndexSpec::model)).await?;
+            return Ok(Vec::new());
+        }
+
+        Ok(missing.iter().map(|index| format!("{}.{}", T::collection_name(), index.name)).collect())
+    }
+
+    /// Returns the indexes required by the collection `T` which don't exist.
+    pub async fn missing_indexes<T>(&self) -> DatabaseResult<Vec<IndexSpec>>
+    where
+        T: CollectionName + Sync + Send,
+    {
+        let existing: Vec<IndexModel> = match self.collection::<T>().list_indexes().await {
+            Ok(cursor) => cursor.try_collect().await?,
+            // The collection is created along with its first index.
+            Err(err) if is_namespace_not_found(&err) => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(T::indexes()
+            .iter()
+            .filter(|spec| !existing.iter().any(|index| spec.is_satisfied_by(index)))
+            .copied()
+            .collect())
+    }
+}
+
+fn is_namespace_not_found(err: &Error) -> bool {
+    matches!(err.kind.as_ref(), ErrorKind::Command(err) if err.code == NAMESPACE_NOT_FOUND_CODE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mongo::{MongoFuzzer, RANDOM_BYTES_SIZE};
+    use mongodb::bson::doc;
+
+    #[test]
+    fn test_index_spec_is_satisfied_by() {
+        // Given
+        let spec = IndexSpec { name: "log_address_topic_0", keys: &["log.address", "log.topics.0"] };
+
+        // When
+        let same_keys = IndexModel::builder().keys(doc! {"log.address": -1, "log.topics.0": 1}).build();
+        let other_order = IndexModel::builder().keys(doc! {"log.topics.0": 1, "log.address": 1}).build();
+        let prefix = IndexModel::builder().keys(doc! {"log.address": 1}).build();
+
+        // Then
+        assert!(spec.is_satisfied_by(&same_keys));
+        assert!(!spec.is_satisfied_by(&other_order));
+        assert!(!spec.is_satisfied_by(&prefix));
+        assert_eq!(spec.keys_document(), doc! {"log.address": 1, "log.topics.0": 1});
+    }
+
+    #[tokio::test]
+    async fn test_ensure_indexes() {
+        // Given
+        let mongo_fuzzer = MongoFuzzer::new(RANDOM_BYTES_SIZE).await;
+        let database = mongo_fuzzer.finalize().await;
+
+        // When
+        let refused = database.ensure_indexes(IndexMode::Refuse).await;
+        database.ensure_indexes(IndexMode::Create).await.unwrap();
+
+        // Then
+        assert!(refused.unwrap_err().to_string().contains("logs.log_topic_0"));
+        database.ensure_indexes(IndexMode::Refuse).await.unwrap();
+        assert!(database.missing_indexes::<StoredLog>().await.unwrap().is_empty());
+    }
+}
@@ -1,16 +1,18 @@
 pub mod ethereum;
 pub mod filter;
+pub mod indexes;
 pub mod state;
 pub mod types;
 
 use super::error::KakarotError;
 use crate::providers::eth_provider::database::types::{
-    header::StoredHeader,
+    header::{StoredHeader, StoredHeaderLogsBloom},
     log::StoredLog,
     receipt::StoredTransactionReceipt,
     transaction::{StoredEthStarknetTransactionHash, StoredTransaction},
 };
 use futures::TryStreamExt;
+use indexes::IndexSpec;
 use itertools::Itertools;
 use mongodb::{
     bson::{doc, Document},
@@ -33,6 +35,13 @@ impl FindOpts {
         self
     }
 
+    /// Sets the sort order of the documents to retrieve.
+    #[must_use]
+    pub fn with_sort(mut self, sort: Document) -> Self {
+        self.0.sort = Some(sort);
+        self
+    }
+
     /// Sets the projection for the documents to retrieve.
     #[must_use]
     pub fn with_projection(mut self, projection: Document) -> Self {
@@ -209,6 +218,11 @@ impl From<MongoDatabase> for Database {
 pub trait CollectionName {
     /// Returns the name of the collection associated with the type
     fn collection_name() -> &'static str;
+
+    /// Returns the indexes of the collection required by the queries on the type
+    fn indexes() -> &'static [IndexSpec] {
+        &[]
+    }
 }
 
 /// Implement [`CollectionName`] for [`StoredHeader`]
@@ -216,6 +230,20 @@ impl CollectionName for StoredHeader {
     fn collection_name() -> &'static str {
         "headers"
     }
+
+    fn indexes() -> &'static [IndexSpec] {
+        &[
+            IndexSpec { name: "header_number", keys: &["header.number"] },
+            IndexSpec { name: "header_hash", keys: &["header.hash"] },
+        ]
+    }
+}
+
+/// Implement [`CollectionName`] for [`StoredHeaderLogsBloom`]
+impl CollectionName for StoredHeaderLogsBloom {
+    fn collection_name() -> &'static str {
+        "headers"
+    }
 }
 
 /// Implement [`CollectionName`] for [`StoredTransaction`]
@@ -223,6 +251,14 @@ impl CollectionName for StoredTransaction {
     fn collection_name() -> &'static str {
         "transactions"
     }
+
+    fn indexes() -> &'static [IndexSpec] {
+        &[
+            IndexSpec { name: "tx_hash", keys: &["tx.hash"] },
+            IndexSpec { name: "tx_block_number", keys: &["tx.blockNumber"] },
+            IndexSpec { name: "tx_block_hash", keys: &["tx.blockHash"] },
+        ]
+    }
 }
 
 /// Implement [`CollectionName`] for [`StoredTransactionReceipt`]
@@ -230,6 +266,14 @@ impl CollectionName for StoredTransactionReceipt {
     fn collection_name() -> &'static str {
         "receipts"
     }
+
+    fn indexes() -> &'static [IndexSpec] {
+        &[
+            IndexSpec { name: "receipt_transaction_hash", keys: &["receipt.transactionHash"] },
+            IndexSpec { name: "receipt_block_number", keys: &["receipt.blockNumber"] },
+            IndexSpec { name: "receipt_block_hash", keys: &["receipt.blockHash"] },
+        ]
+    }
 }
 
 /// Implement [`CollectionName`] for [`StoredLog`]
@@ -237,6 +281,16 @@ impl CollectionName for StoredLog {
     fn collection_name() -> &'static str {
         "logs"
     }
+
+    fn indexes() -> &'static [IndexSpec] {
+        &[
+            IndexSpec { name: "log_block_number", keys: &["log.blockNumber"] },
+            IndexSpec { name: "log_block_hash", keys: &["log.blockHash"] },
+            IndexSpec { name: "log_address", keys: &["log.address"] },
+            // The topics are filtered by position, which a multikey index on the topics can't serve.
+            IndexSpec { name: "log_topic_0", keys: &["log.topics.0"] },
+        ]
+    }
 }
 
 /// Implement [`CollectionName`] for [`StoredEthStarknetTransactionHash`]
@@ -244,4 +298,11 @@ impl CollectionName for StoredEthStarknetTransactionHash {
     fn collection_name() -> &'static str {
         "transaction_hashes"
     }
+
+    fn indexes() -> &'static [IndexSpec] {
+        &[
+            IndexSpec { name: "hashes_eth_hash", keys: &["hashes.eth_hash"] },
+            IndexSpec { name: "hashes_starknet_hash", keys: &["hashes.starknet_hash"] },
+        ]
+    }
 }
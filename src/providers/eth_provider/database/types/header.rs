@@ -1,4 +1,5 @@
 use super::transaction::ExtendedTransaction;
+use alloy_primitives::Bloom;
 use alloy_rpc_types::{Block, Header};
 use alloy_serde::WithOtherFields;
 use serde::{Deserialize, Serialize};
@@ -40,6 +41,23 @@ impl Deref for StoredHeader {
     }
 }
 
+/// The number and logs bloom of a header as stored in the database, used to select the blocks
+/// which can contain logs matching a filter without fetching the full headers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct StoredHeaderLogsBloom {
+    #[serde(deserialize_with = "crate::providers::eth_provider::database::types::serde::deserialize_intermediate")]
+    pub header: HeaderLogsBloom,
+}
+
+/// The number and logs bloom of a header.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderLogsBloom {
+    #[serde(with = "alloy_serde::quantity")]
+    pub number: u64,
+    pub logs_bloom: Bloom,
+}
+
 #[cfg(any(test, feature = "arbitrary", feature = "testing"))]
 impl Arbitrary<'_> for StoredHeader {
     fn arbitrary(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
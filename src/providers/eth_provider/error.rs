@@ -1,4 +1,4 @@
-use alloy_primitives::{Bytes, B256};
+use alloy_primitives::{Bytes, B256, U64};
 use alloy_rpc_types::BlockHashOrNumber;
 use alloy_sol_types::decode_revert_reason;
 use jsonrpsee::types::ErrorObject;
@@ -40,9 +40,11 @@ impl From<&EthApiError> for EthRpcErrorCode {
             | EthApiError::CalldataExceededLimit(_, _)
             | EthApiError::RethEthApi(_) => Self::InvalidParams,
             EthApiError::Transaction(err) => err.into(),
+            EthApiError::FilterNotFound(_) => Self::InvalidInput,
             // TODO improve the error
             EthApiError::Unsupported(_) | EthApiError::Kakarot(_) | EthApiError::Pool(_) => Self::InternalError,
             EthApiError::Execution(_) => Self::ExecutionError,
+            EthApiError::LogsBlockRangeTooLarge(_) | EthApiError::LogsLimitExceeded(_, _) => Self::RequestLimitExceeded,
         }
     }
 }
@@ -62,6 +64,8 @@ pub enum EthApiError {
     UnknownBlockNumber(Option<u64>),
     /// When a transaction is not found
     TransactionNotFound(B256),
+    /// When a filter is not found or has been evicted
+    FilterNotFound(U64),
     /// Error related to transaction
     Transaction(#[from] TransactionError),
     /// Error related to transaction pool
@@ -78,6 +82,11 @@ pub enum EthApiError {
     Kakarot(KakarotError),
     /// Error related to transaction calldata being too large.
     CalldataExceededLimit(usize, usize),
+    /// When the block range of a logs query exceeds the maximum range
+    LogsBlockRangeTooLarge(u64),
+    /// When a logs query matches more logs than the limit, along with
+    /// a block range matching less logs if the query has a block range
+    LogsLimitExceeded(u64, Option<(u64, u64)>),
     /// Reth Eth API error
     RethEthApi(#[from] RethEthApiError),
 }
@@ -88,6 +97,7 @@ impl std::fmt::Display for EthApiError {
             Self::UnknownBlock(block) => write!(f, "unknown block {block}"),
             Self::UnknownBlockNumber(block) => write!(f, "unknown block number {block:?}"),
             Self::TransactionNotFound(tx) => write!(f, "transaction not found {tx}"),
+            Self::FilterNotFound(_) => write!(f, "filter not found"),
             Self::Transaction(err) => write!(f, "{err}"),
             Self::Pool(err) => write!(f, "{err}"),
             Self::Signature(err) => write!(f, "{err}"),
@@ -103,6 +113,13 @@ impl std::fmt::Display for EthApiError {
             Self::CalldataExceededLimit(limit, actual) => {
                 write!(f, "calldata exceeded limit of {limit}: {actual}")
             }
+            Self::LogsBlockRangeTooLarge(max_range) => {
+                write!(f, "block range too large, the maximum block range is {max_range}")
+            }
+            Self::LogsLimitExceeded(limit, None) => write!(f, "query returned more than {limit} results"),
+            Self::LogsLimitExceeded(limit, Some((from, to))) => {
+                write!(f, "query returned more than {limit} results, try with this block range [{from:#x}, {to:#x}]")
+            }
         }
     }
 }
@@ -147,6 +164,9 @@ pub enum KakarotError {
     /// Error related to the database deserialization.
     #[error(transparent)]
     DatabaseDeserialization(#[from] mongodb::bson::de::Error),
+    /// Error related to the Starknet storage proof request.
+    #[error("storage proof error: {0}")]
+    StorageProof(String),
 }
 
 impl From<KakarotError> for EthApiError {
@@ -304,6 +324,9 @@ pub enum TransactionError {
     /// Thrown when the gas limit exceeds the block's gas limit.
     #[error("transaction gas limit {0} exceeds block gas limit {1}")]
     ExceedsBlockGasLimit(u128, u128),
+    /// Thrown when the transaction fails even with the highest gas limit the sender can pay for.
+    #[error("gas required exceeds allowance ({0})")]
+    GasRequiredExceedsAllowance(u64),
     /// Thrown when the transaction isn't the
     /// [`BlockTransactions::FullTransactions`] variant.
     #[error("expected full transactions")]
@@ -322,7 +345,9 @@ pub enum TransactionError {
 impl From<&TransactionError> for EthRpcErrorCode {
     fn from(error: &TransactionError) -> Self {
         match error {
-            TransactionError::InvalidChainId | TransactionError::InvalidTransactionType => Self::InvalidInput,
+            TransactionError::InvalidChainId
+            | TransactionError::InvalidTransactionType
+            | TransactionError::GasRequiredExceedsAllowance(_) => Self::InvalidInput,
             TransactionError::GasOverflow
             | TransactionError::FeeCapTooLow(_, _)
             | TransactionError::TipAboveFeeCap(_, _) => Self::TransactionRejected,
@@ -1,29 +1,66 @@
 use super::{
     constant::BLOCK_NUMBER_HEX_STRING_LEN,
-    error::{ExecutionError, KakarotError},
+    database::state::{EthCacheDatabase, EthDatabase},
+    error::{EthApiError, EvmError, ExecutionError, KakarotError, TransactionError},
     starknet::kakarot_core::{core::KakarotCoreReader, KAKAROT_ADDRESS},
+    StateProvider,
 };
 use crate::{
+    constants::KKRT_BLOCK_GAS_LIMIT,
     into_via_wrapper,
     providers::eth_provider::{
-        database::{filter::format_hex, types::header::StoredHeader},
+        database::{
+            filter::{self, format_hex, EthDatabaseFilterBuilder},
+            types::{
+                header::StoredHeader,
+                receipt::{ExtendedTxReceipt, StoredTransactionReceipt},
+            },
+        },
         provider::{EthApiResult, EthDataProvider},
     },
 };
-use alloy_eips::{BlockId, BlockNumberOrTag};
-use alloy_primitives::{U256, U64};
-use alloy_rpc_types::{FeeHistory, TransactionRequest};
+use alloy_eips::{
+    eip1559::{calc_next_block_base_fee, BaseFeeParams},
+    BlockId, BlockNumberOrTag,
+};
+use alloy_primitives::{B256, U256, U64};
+use alloy_rpc_types::{state::StateOverride, FeeHistory, TransactionRequest};
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use eyre::eyre;
 use mongodb::bson::doc;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_node_api::ConfigureEvm;
+use reth_revm::{
+    db::CacheDB,
+    primitives::{EVMError, ExecutionResult, HaltReason, InvalidTransaction},
+    DatabaseRef,
+};
+use reth_rpc_eth_types::{error::ensure_success, revm_utils::apply_state_overrides, EthApiError as RethEthApiError};
+use std::{collections::HashMap, sync::Arc};
 use tracing::Instrument;
 
+/// Gas estimation stops once the gas limit is within 1.5% of the lowest successful gas limit,
+/// as done by Geth.
+const ESTIMATE_GAS_ERROR_RATIO_PER_MILLE: u128 = 15;
+
+/// Maximum number of blocks of a fee history, larger requests are truncated as done by Geth.
+const MAX_FEE_HISTORY_BLOCK_COUNT: u64 = 1024;
+
+/// Maximum number of reward percentiles of a fee history.
+const MAX_REWARD_PERCENTILES: usize = 100;
+
 #[async_trait]
 #[auto_impl(Arc, &)]
 pub trait GasProvider {
-    /// Returns the result of a estimate gas.
-    async fn estimate_gas(&self, call: TransactionRequest, block_id: Option<BlockId>) -> EthApiResult<U256>;
+    /// Returns the lowest gas limit with which the request succeeds, optionally on top of the
+    /// overridden state.
+    async fn estimate_gas(
+        &self,
+        call: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<U256>;
 
     /// Returns the fee history given a block count and a newest block number.
     async fn fee_history(
@@ -42,43 +79,89 @@ impl<SP> GasProvider for EthDataProvider<SP>
 where
     SP: starknet::providers::Provider + Send + Sync,
 {
-    async fn estimate_gas(&self, request: TransactionRequest, block_id: Option<BlockId>) -> EthApiResult<U256> {
-        // Set a high gas limit to make sure the transaction will not fail due to gas.
-        let request = TransactionRequest { gas: Some(u64::MAX), ..request };
+    async fn estimate_gas(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<U256> {
+        // The Kakarot contract can't execute the request on top of the overridden state.
+        if let Some(state_overrides) = state_overrides {
+            return Ok(U256::from(self.estimate_gas_with_state_overrides(request, block_id, state_overrides).await?));
+        }
 
-        let gas_used = self.estimate_gas_inner(request, block_id).await?;
+        let mut hi = request.gas.unwrap_or(KKRT_BLOCK_GAS_LIMIT);
+        if let Some(from) = request.from {
+            let balance = self.balance(from, block_id).await?;
+            let gas_price = request.max_fee_per_gas.or(request.gas_price).unwrap_or_default();
+            if let Some(allowance) = gas_allowance(balance, request.value.unwrap_or_default(), U256::from(gas_price)) {
+                hi = hi.min(allowance);
+            }
+        }
 
-        // Increase the gas used by 40% to make sure the transaction will not fail due to gas.
-        // This is a temporary solution until we have a proper gas estimation.
-        // Does not apply to Hive feature otherwise end2end tests will fail.
-        let gas_used = if cfg!(feature = "hive") { gas_used } else { gas_used * 140 / 100 };
-        Ok(U256::from(gas_used))
+        let required_gas =
+            match self.estimate_gas_inner(TransactionRequest { gas: Some(hi), ..request.clone() }, block_id).await {
+                Ok(required_gas) => u64::try_from(required_gas).map_err(|_| TransactionError::GasOverflow)?,
+                Err(EthApiError::Execution(ExecutionError::Evm(EvmError::OutOfGas))) => {
+                    return Err(TransactionError::GasRequiredExceedsAllowance(hi).into())
+                }
+                Err(err) => return Err(err),
+            };
+
+        let mut search = GasSearch::new(required_gas, hi);
+        while let Some(gas_limit) = search.next_gas_limit() {
+            let request = TransactionRequest { gas: Some(gas_limit), ..request.clone() };
+            let success = match self.call_inner(request, block_id).await {
+                Ok(_) => true,
+                // Only the EVM failures depend on the gas limit, the other errors are returned.
+                Err(EthApiError::Execution(ExecutionError::Evm(_))) => false,
+                Err(err) => return Err(err),
+            };
+            search.update(gas_limit, success);
+        }
+
+        Ok(U256::from(search.hi))
     }
 
     async fn fee_history(
         &self,
         block_count: U64,
         newest_block: BlockNumberOrTag,
-        _reward_percentiles: Option<Vec<f64>>,
+        reward_percentiles: Option<Vec<f64>>,
     ) -> EthApiResult<FeeHistory> {
         if block_count == U64::ZERO {
             return Ok(FeeHistory::default());
         }
 
+        // At most 100 percentiles, between 0 and 100 and in ascending order.
+        if let Some(percentiles) = &reward_percentiles {
+            if percentiles.len() > MAX_REWARD_PERCENTILES
+                || percentiles.iter().any(|p| !(0. ..=100.).contains(p))
+                || percentiles.windows(2).any(|w| w[0] > w[1])
+            {
+                return Err(RethEthApiError::InvalidRewardPercentiles.into());
+            }
+        }
+        let block_count = block_count.saturating_to::<u64>().min(MAX_FEE_HISTORY_BLOCK_COUNT);
+
         let end_block = self.tag_into_block_number(newest_block).await?;
         let end_block_plus_one = end_block.saturating_add(1);
 
         // 0 <= start_block <= end_block
-        let start_block = end_block_plus_one.saturating_sub(block_count.to());
+        let start_block = end_block_plus_one.saturating_sub(block_count);
 
         let header_filter = doc! {"$and": [ { "header.number": { "$gte": format_hex(start_block, BLOCK_NUMBER_HEX_STRING_LEN) } }, { "header.number": { "$lte": format_hex(end_block, BLOCK_NUMBER_HEX_STRING_LEN) } } ] };
-        let blocks: Vec<StoredHeader> = self.database().get(header_filter, None).await?;
+        let mut blocks: Vec<StoredHeader> = self.database().get(header_filter, None).await?;
+        // The headers collection can contain a block multiple times, the sealed header is preferred
+        // over the pending one which hash is zero.
+        blocks.sort_by_key(|header| (header.number, header.hash.is_zero()));
+        blocks.dedup_by_key(|header| header.number);
 
-        if blocks.is_empty() {
+        let Some(last_block) = blocks.last() else {
             return Err(
                 KakarotError::from(mongodb::error::Error::custom(eyre!("No blocks found in the database"))).into()
             );
-        }
+        };
 
         let gas_used_ratio = blocks
             .iter()
@@ -92,17 +175,33 @@ where
             })
             .collect();
 
-        let mut base_fee_per_gas =
-            blocks.iter().map(|header| header.base_fee_per_gas.unwrap_or_default()).collect::<Vec<_>>();
-        // TODO(EIP1559): Remove this when proper base fee computation: if gas_ratio > 50%, increase base_fee_per_gas
-        base_fee_per_gas.extend_from_within((base_fee_per_gas.len() - 1)..);
+        // The base fee of the block following the range is derived from the gas used by the last block.
+        let next_base_fee = calc_next_block_base_fee(
+            last_block.gas_used,
+            last_block.gas_limit,
+            last_block.base_fee_per_gas.unwrap_or_default(),
+            BaseFeeParams::ethereum(),
+        );
+        let base_fee_per_gas = blocks
+            .iter()
+            .map(|header| header.base_fee_per_gas.unwrap_or_default())
+            .chain(std::iter::once(next_base_fee))
+            .map(Into::into)
+            .collect::<Vec<_>>();
+
+        let reward = match reward_percentiles {
+            Some(percentiles) => Some(self.rewards(&blocks, start_block, end_block, &percentiles).await?),
+            None => None,
+        };
 
+        // Kakarot doesn't support blob transactions.
         Ok(FeeHistory {
-            base_fee_per_gas: base_fee_per_gas.into_iter().map(Into::into).collect(),
+            base_fee_per_blob_gas: vec![0; base_fee_per_gas.len()],
+            blob_gas_used_ratio: vec![0.; blocks.len()],
+            base_fee_per_gas,
             gas_used_ratio,
             oldest_block: start_block,
-            reward: Some(vec![]),
-            ..Default::default()
+            reward,
         })
     }
 
@@ -114,3 +213,231 @@ where
         Ok(into_via_wrapper!(gas_price))
     }
 }
+
+impl<SP> EthDataProvider<SP>
+where
+    SP: starknet::providers::Provider + Send + Sync,
+{
+    /// Returns the effective priority fees per gas at the percentiles of the gas used by each
+    /// block, from the receipts of the blocks between `start_block` and `end_block`.
+    async fn rewards(
+        &self,
+        blocks: &[StoredHeader],
+        start_block: u64,
+        end_block: u64,
+        percentiles: &[f64],
+    ) -> EthApiResult<Vec<Vec<u128>>> {
+        let filter = EthDatabaseFilterBuilder::<filter::Receipt>::default()
+            .with_block_number_range(start_block, end_block)
+            .build();
+        let receipts: Vec<StoredTransactionReceipt> = self.database().get(filter, None).await?;
+
+        // The receipts are keyed by block hash so that the receipts of the pending duplicate of a
+        // sealed block, which hash is zero, are not counted with the ones of the sealed block.
+        let mut receipts_by_block: HashMap<B256, Vec<ExtendedTxReceipt>> = HashMap::new();
+        for receipt in receipts {
+            let receipt = ExtendedTxReceipt::from(receipt);
+            receipts_by_block.entry(receipt.block_hash.unwrap_or_default()).or_default().push(receipt);
+        }
+
+        Ok(blocks
+            .iter()
+            .map(|header| {
+                let base_fee = u128::from(header.base_fee_per_gas.unwrap_or_default());
+                let rewards = receipts_by_block
+                    .remove(&header.hash)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|receipt| (receipt.effective_gas_price.saturating_sub(base_fee), receipt.gas_used))
+                    .collect();
+                reward_percentiles(rewards, percentiles)
+            })
+            .collect())
+    }
+
+    /// Estimates the gas of the request by executing it on top of the overridden state.
+    async fn estimate_gas_with_state_overrides(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_overrides: StateOverride,
+    ) -> EthApiResult<u64> {
+        let block_id = block_id.unwrap_or_default();
+        let mut env = self.call_env(&request, block_id).await?;
+        let mut db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id)));
+        apply_state_overrides(state_overrides, &mut db.0)?;
+
+        let mut hi = request.gas.unwrap_or_else(|| env.block.gas_limit.saturating_to());
+        if let Some(from) = request.from {
+            let balance = db.0.basic_ref(from)?.map(|account| account.balance).unwrap_or_default();
+            if let Some(allowance) = gas_allowance(balance, env.tx.value, env.tx.gas_price) {
+                hi = hi.min(allowance);
+            }
+        }
+
+        let evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+        let mut transact = |gas_limit: u64| {
+            env.tx.gas_limit = gas_limit;
+            evm_config.evm_with_env(&mut db.0, env.clone()).transact().map(|res| res.result)
+        };
+
+        let result = match transact(hi) {
+            Ok(ExecutionResult::Halt { reason: HaltReason::OutOfGas(_), .. })
+            | Err(EVMError::Transaction(InvalidTransaction::CallGasCostMoreThanGasLimit)) => {
+                return Err(TransactionError::GasRequiredExceedsAllowance(hi).into())
+            }
+            result => result.map_err(|err| TransactionError::Call(err.into()))?,
+        };
+        let required_gas = result.gas_used();
+        ensure_success(result)?;
+
+        let mut search = GasSearch::new(required_gas, hi);
+        while let Some(gas_limit) = search.next_gas_limit() {
+            let success = transact(gas_limit).map_err(|err| TransactionError::Call(err.into()))?.is_success();
+            search.update(gas_limit, success);
+        }
+
+        Ok(search.hi)
+    }
+}
+
+/// Returns the rewards at the percentiles of the gas used, given the reward and the gas used
+/// of each transaction of a block, as done by Geth.
+fn reward_percentiles(mut rewards: Vec<(u128, u128)>, percentiles: &[f64]) -> Vec<u128> {
+    if rewards.is_empty() {
+        return vec![0; percentiles.len()];
+    }
+
+    rewards.sort_unstable_by_key(|(reward, _)| *reward);
+    let total_gas_used = rewards.iter().map(|(_, gas_used)| gas_used).sum::<u128>() as f64;
+
+    let mut index = 0;
+    let mut cumulative_gas_used = rewards[0].1;
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = total_gas_used * percentile / 100.;
+            while (cumulative_gas_used as f64) < threshold && index < rewards.len() - 1 {
+                index += 1;
+                cumulative_gas_used += rewards[index].1;
+            }
+            rewards[index].0
+        })
+        .collect()
+}
+
+/// Returns the highest gas limit the sender can pay for with its balance, or `None` if the
+/// request doesn't pay for gas.
+fn gas_allowance(balance: U256, value: U256, gas_price: U256) -> Option<u64> {
+    (!gas_price.is_zero()).then(|| (balance.saturating_sub(value) / gas_price).saturating_to())
+}
+
+/// Binary search of the lowest gas limit with which a request succeeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GasSearch {
+    /// Highest gas limit with which the request fails.
+    lo: u64,
+    /// Lowest gas limit with which the request succeeds.
+    hi: u64,
+    /// Gas used by the request, tried first as it is usually enough.
+    required_gas: Option<u64>,
+}
+
+impl GasSearch {
+    /// Starts the search from the gas used by the request when executed with the `hi` gas limit.
+    const fn new(required_gas: u64, hi: u64) -> Self {
+        Self { lo: required_gas.saturating_sub(1), hi, required_gas: Some(required_gas) }
+    }
+
+    /// Returns the next gas limit to try, or `None` once the search is over.
+    fn next_gas_limit(&mut self) -> Option<u64> {
+        if self.lo.saturating_add(1) >= self.hi {
+            return None;
+        }
+        if let Some(required_gas) = self.required_gas.take() {
+            return Some(required_gas);
+        }
+        if u128::from(self.hi - self.lo) * 1000 < u128::from(self.hi) * ESTIMATE_GAS_ERROR_RATIO_PER_MILLE {
+            return None;
+        }
+        Some(self.lo + (self.hi - self.lo) / 2)
+    }
+
+    /// Updates the bounds of the search with the outcome of the request at the gas limit.
+    fn update(&mut self, gas_limit: u64, success: bool) {
+        if success {
+            self.hi = gas_limit;
+        } else {
+            self.lo = gas_limit;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_allowance() {
+        // Given
+        let balance = U256::from(1_000_000);
+        let value = U256::from(100_000);
+
+        // When
+        let allowance = gas_allowance(balance, value, U256::from(10));
+        let free = gas_allowance(balance, value, U256::ZERO);
+
+        // Then
+        assert_eq!(allowance, Some(90_000));
+        assert_eq!(free, None);
+    }
+
+    #[test]
+    fn test_reward_percentiles() {
+        // Given
+        let rewards = vec![(30, 50_000), (10, 21_000), (20, 29_000)];
+        let percentiles = [0., 21., 50., 50.1, 100.];
+
+        // When
+        let block_rewards = reward_percentiles(rewards, &percentiles);
+        let empty_block_rewards = reward_percentiles(vec![], &percentiles);
+
+        // Then
+        assert_eq!(block_rewards, vec![10, 10, 20, 30, 30]);
+        assert_eq!(empty_block_rewards, vec![0; 5]);
+    }
+
+    #[test]
+    fn test_gas_search_required_gas_is_enough() {
+        // Given
+        let mut search = GasSearch::new(50_000, 7_000_000);
+
+        // When
+        let gas_limit = search.next_gas_limit();
+        search.update(50_000, true);
+
+        // Then
+        assert_eq!(gas_limit, Some(50_000));
+        assert_eq!(search.next_gas_limit(), None);
+        assert_eq!(search.hi, 50_000);
+    }
+
+    #[test]
+    fn test_gas_search_converges() {
+        // Given
+        let minimal_gas_limit = 63_500;
+        let mut search = GasSearch::new(50_000, 7_000_000);
+
+        // When
+        while let Some(gas_limit) = search.next_gas_limit() {
+            search.update(gas_limit, gas_limit >= minimal_gas_limit);
+        }
+
+        // Then
+        assert!(search.hi >= minimal_gas_limit);
+        assert!(
+            u128::from(search.hi - minimal_gas_limit) * 1000
+                < u128::from(search.hi) * ESTIMATE_GAS_ERROR_RATIO_PER_MILLE
+        );
+    }
+}
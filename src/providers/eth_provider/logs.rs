@@ -1,6 +1,9 @@
 use super::{
-    constant::MAX_LOGS,
-    database::{filter::EthDatabaseFilterBuilder, types::log::StoredLog},
+    constant::{MAX_LOGS, MAX_LOGS_BLOCK_RANGE},
+    database::{
+        filter::EthDatabaseFilterBuilder,
+        types::{header::StoredHeaderLogsBloom, log::StoredLog},
+    },
     error::EthApiError,
 };
 use crate::providers::eth_provider::{
@@ -11,9 +14,15 @@ use crate::providers::eth_provider::{
     provider::{EthApiResult, EthDataProvider},
     BlockProvider,
 };
-use alloy_rpc_types::{Filter, FilterChanges};
+use alloy_rpc_types::{Filter, FilterChanges, FilteredParams, Log, Topic};
 use async_trait::async_trait;
 use auto_impl::auto_impl;
+use mongodb::bson::doc;
+
+/// Maximum number of blocks of a range which logs blooms are read to select the candidate blocks.
+/// It bounds the headers loaded in memory, and keeps the listed candidate blocks far below the
+/// 16 MiB limit of the BSON documents.
+const MAX_BLOOM_FILTERED_BLOCKS: u64 = 10_000;
 
 #[async_trait]
 #[auto_impl(Arc, &)]
@@ -31,6 +40,7 @@ where
 
         // Create the database filter.
         let mut builder = EthDatabaseFilterBuilder::<filter::Log>::default();
+        let mut block_range = None;
         builder = if block_hash.is_some() {
             // We filter by block hash on matching the exact block hash.
             builder.with_block_hash(&block_hash.unwrap())
@@ -47,15 +57,22 @@ where
                 (from, to) if to > current_block => (from, current_block),
                 other => other,
             };
-            // We filter by block number using $gte and $lte.
-            builder.with_block_number_range(from, to)
-        };
+            if let Some(max_range) = *MAX_LOGS_BLOCK_RANGE {
+                if to - from >= max_range {
+                    return Err(EthApiError::LogsBlockRangeTooLarge(max_range));
+                }
+            }
+            block_range = Some((from, to));
 
-        // TODO: this will work for now but isn't very efficient. Would need to:
-        // 1. Create the bloom filter from the topics
-        // 2. Query the database for logs within block range with the bloom filter
-        // 3. Filter this reduced set of logs by the topics
-        // 4. Limit the number of logs returned
+            match self.bloom_candidate_blocks(&filter, from, to).await? {
+                // No block of the range can contain a matching log.
+                Some(blocks) if blocks.is_empty() => return Ok(FilterChanges::Logs(vec![])),
+                // We only query the logs of the blocks which bloom matches the filter.
+                Some(blocks) => builder.with_block_numbers(&blocks),
+                // We filter by block number using $gte and $lte.
+                None => builder.with_block_number_range(from, to),
+            }
+        };
 
         // Convert the topics to a MongoDB filter and add it to the database filter
         builder = builder.with_topics(&filter.topics);
@@ -63,13 +80,71 @@ where
         // Add the addresses
         builder = builder.with_addresses(&filter.address.into_iter().collect::<Vec<_>>());
 
-        Ok(FilterChanges::Logs(
-            self.database()
-                .get_and_map_to::<_, StoredLog>(
-                    builder.build(),
-                    (*MAX_LOGS).map(|limit| FindOpts::default().with_limit(limit)),
-                )
-                .await?,
-        ))
+        // Fetch one more log than the limit in order to detect the queries matching too many logs.
+        // Block numbers are stored padded, so sorting on them orders the logs by block.
+        let mut find_options = FindOpts::default().with_sort(doc! {"log.blockNumber": 1});
+        if let Some(limit) = *MAX_LOGS {
+            find_options = find_options.with_limit(limit.saturating_add(1));
+        }
+        let mut logs: Vec<Log> =
+            self.database().get_and_map_to::<_, StoredLog>(builder.build(), Some(find_options)).await?;
+
+        if let Some(limit) = *MAX_LOGS {
+            if let Some(first_excluded_log) = logs.get(limit as usize) {
+                // Suggest the range ending before the block of the first log over the limit, which
+                // matches at most `limit` logs, unless the logs of the first block exceed the limit.
+                let suggested_range = block_range.map(|(from, _)| {
+                    let first_excluded_block = first_excluded_log.block_number.unwrap_or_default();
+                    (from, first_excluded_block.saturating_sub(1).max(from))
+                });
+                return Err(EthApiError::LogsLimitExceeded(limit, suggested_range));
+            }
+        }
+
+        // Deterministic ordering, allowing clients to paginate over the block range.
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        Ok(FilterChanges::Logs(logs))
+    }
+}
+
+impl<SP> EthDataProvider<SP>
+where
+    SP: starknet::providers::Provider + Send + Sync,
+{
+    /// Returns the numbers of the blocks in the range which logs bloom matches the addresses and
+    /// topics of the filter, or `None` if the filter doesn't restrict the addresses nor the topics
+    /// or if the range is too large to read the blooms of all its blocks.
+    ///
+    /// The blooms can contain false positives, the logs of the candidate blocks still need to be
+    /// filtered on the addresses and topics.
+    async fn bloom_candidate_blocks(&self, filter: &Filter, from: u64, to: u64) -> EthApiResult<Option<Vec<u64>>> {
+        if filter.address.is_empty() && filter.topics.iter().all(Topic::is_empty) {
+            return Ok(None);
+        }
+        if to - from >= MAX_BLOOM_FILTERED_BLOCKS {
+            return Ok(None);
+        }
+
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        let header_filter = EthDatabaseFilterBuilder::<filter::Header>::default().with_block_number_range(from, to);
+        let find_options = FindOpts::default().with_projection(doc! {"header.number": 1, "header.logsBloom": 1});
+        let headers: Vec<StoredHeaderLogsBloom> = self.database().get(header_filter.build(), find_options).await?;
+
+        let mut blocks: Vec<_> = headers
+            .into_iter()
+            .filter(|stored| {
+                FilteredParams::matches_address(stored.header.logs_bloom, &address_filter)
+                    && FilteredParams::matches_topics(stored.header.logs_bloom, &topics_filter)
+            })
+            .map(|stored| stored.header.number)
+            .collect();
+        // The headers collection can contain a block multiple times.
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        Ok(Some(blocks))
     }
 }
@@ -1,4 +1,5 @@
 pub mod blocks;
+pub mod bundle;
 pub mod chain;
 pub mod constant;
 pub mod contracts;
@@ -8,8 +9,10 @@ pub mod gas;
 pub mod logs;
 pub mod provider;
 pub mod receipts;
+pub mod simulate;
 pub mod starknet;
 pub mod state;
+pub mod state_cache;
 pub mod transactions;
 pub mod tx_pool;
 pub mod utils;
@@ -7,9 +7,10 @@ use super::{
         core::{CallInput, KakarotCoreReader, Uint256},
         KAKAROT_ADDRESS,
     },
+    state_cache::StateCache,
 };
 use crate::{
-    constants::ETH_CHAIN_ID,
+    constants::{ETH_CHAIN_ID, KAKAROT_RPC_CONFIG},
     into_via_try_wrapper, into_via_wrapper,
     models::block::{EthBlockId, EthBlockNumberOrTag},
     providers::{
@@ -18,7 +19,7 @@ use crate::{
     },
 };
 use alloy_eips::{BlockId, BlockNumberOrTag};
-use alloy_primitives::{TxKind, U256};
+use alloy_primitives::{Bytes, TxKind, U256};
 use alloy_rpc_types::{BlockHashOrNumber, TransactionRequest};
 use cainome::cairo_serde::CairoArrayLegacy;
 use eyre::Result;
@@ -27,6 +28,7 @@ use mongodb::bson::doc;
 use num_traits::cast::ToPrimitive;
 use starknet::core::types::Felt;
 use tracing::{instrument, Instrument};
+use url::Url;
 #[cfg(feature = "hive")]
 use {
     crate::providers::eth_provider::error::SignatureError,
@@ -61,6 +63,12 @@ impl<T> EthereumProvider for T where
 pub struct EthDataProvider<SP: starknet::providers::Provider + Send + Sync> {
     database: Database,
     starknet_provider: StarknetProvider<SP>,
+    /// Balances, nonces and storage values read from Starknet.
+    state_cache: StateCache<U256>,
+    /// Bytecodes read from Starknet.
+    code_cache: StateCache<Bytes>,
+    /// URL of the Starknet node queried for the storage proofs.
+    storage_proof_url: Url,
     pub chain_id: u64,
 }
 
@@ -89,7 +97,46 @@ where
     SP: starknet::providers::Provider + Send + Sync,
 {
     pub fn new(database: Database, starknet_provider: StarknetProvider<SP>) -> Self {
-        Self { database, starknet_provider, chain_id: *ETH_CHAIN_ID }
+        Self {
+            database,
+            starknet_provider,
+            state_cache: StateCache::new(0),
+            code_cache: StateCache::new(0),
+            storage_proof_url: KAKAROT_RPC_CONFIG.network_url.clone(),
+            chain_id: *ETH_CHAIN_ID,
+        }
+    }
+
+    /// Queries the storage proofs from the Starknet node at the given URL instead of the
+    /// configured Starknet network.
+    #[must_use]
+    pub fn with_storage_proof_url(mut self, url: Url) -> Self {
+        self.storage_proof_url = url;
+        self
+    }
+
+    /// Caches up to `max_state_entries` state values and `max_code_entries` bytecodes read from
+    /// Starknet at sealed blocks.
+    #[must_use]
+    pub fn with_state_cache(mut self, max_state_entries: u32, max_code_entries: u32) -> Self {
+        self.state_cache = StateCache::new(max_state_entries);
+        self.code_cache = StateCache::new(max_code_entries);
+        self
+    }
+
+    /// Returns a reference to the cache of the balances, nonces and storage values.
+    pub(crate) const fn state_cache(&self) -> &StateCache<U256> {
+        &self.state_cache
+    }
+
+    /// Returns the URL of the Starknet node queried for the storage proofs.
+    pub(crate) const fn storage_proof_url(&self) -> &Url {
+        &self.storage_proof_url
+    }
+
+    /// Returns a reference to the cache of the bytecodes.
+    pub(crate) const fn code_cache(&self) -> &StateCache<Bytes> {
+        &self.code_cache
     }
 
     /// Prepare the call input for an estimate gas or call from a transaction request.
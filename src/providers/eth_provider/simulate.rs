@@ -0,0 +1,309 @@
+use super::{
+    constant::CALL_REQUEST_GAS_LIMIT,
+    database::{
+        state::{EthCacheDatabase, EthDatabase},
+        types::header::ExtendedBlock,
+    },
+    error::{EthRpcErrorCode, EvmError, ExecutionError, TransactionError},
+    provider::{EthApiResult, EthDataProvider, EthereumProvider},
+    state::call_env_at,
+    utils::tx_env_from_request,
+    ChainProvider,
+};
+use alloy_eips::BlockId;
+use alloy_primitives::{Bloom, B256, B64, U256};
+use alloy_rpc_types::{
+    simulate::{SimBlock, SimCallResult, SimulateError, SimulatePayload, SimulatedBlock},
+    Block, BlockTransactions, Header, Log, TransactionRequest,
+};
+use alloy_serde::WithOtherFields;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_node_api::ConfigureEvm;
+use reth_revm::{
+    db::CacheDB,
+    primitives::{BlockEnv, EnvWithHandlerCfg, ExecutionResult},
+};
+use reth_rpc_eth_types::{
+    revm_utils::{apply_block_overrides, apply_state_overrides},
+    EthApiError as RethEthApiError,
+};
+use revm_inspectors::transfer::TransferInspector;
+use std::sync::Arc;
+
+/// Maximum number of blocks simulated by a single request.
+pub const MAX_SIMULATED_BLOCKS: usize = 256;
+
+/// Maximum number of calls simulated by a single request, across all its blocks.
+pub const MAX_SIMULATED_CALLS: usize = 1_000;
+
+/// Seconds between two simulated blocks without timestamp override, as done by Geth.
+const SIMULATED_BLOCK_TIME: u64 = 12;
+
+/// Geth error code of the calls halted by the EVM.
+const VM_ERROR_CODE: i32 = -32015;
+
+impl<SP> EthDataProvider<SP>
+where
+    SP: starknet::providers::Provider + Send + Sync,
+{
+    /// Simulates the blocks of calls on top of the state of the given block. Each call is executed
+    /// on top of the state changes of the previous calls, in the same or in a previous block.
+    pub(crate) async fn simulate(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<Vec<SimulatedBlock<ExtendedBlock>>> {
+        let SimulatePayload { block_state_calls, trace_transfers, validation, .. } = payload;
+        if block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+            return Err(RethEthApiError::InvalidParams(format!(
+                "too many blocks, the maximum is {MAX_SIMULATED_BLOCKS}"
+            ))
+            .into());
+        }
+        let call_count = block_state_calls.iter().map(|block| block.calls.len()).sum::<usize>();
+        if call_count > MAX_SIMULATED_CALLS {
+            return Err(RethEthApiError::InvalidParams(format!(
+                "too many calls, the maximum is {MAX_SIMULATED_CALLS}"
+            ))
+            .into());
+        }
+
+        let block_id = block_id.unwrap_or_default();
+        let mut parent = self.call_header(block_id).await?;
+        let chain_id = self.chain_id().await?.unwrap_or_default().to();
+        let base_fee = U256::from(parent.base_fee_per_gas.unwrap_or_default());
+        let mut db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id)));
+        // The gas of all the calls is capped, as the gas of a single call.
+        let mut remaining_gas = CALL_REQUEST_GAS_LIMIT;
+
+        let mut blocks = Vec::with_capacity(block_state_calls.len());
+        for SimBlock { block_overrides, state_overrides, calls } in block_state_calls {
+            let mut env = call_env_at(&parent, &TransactionRequest::default(), chain_id);
+            env.block.number = U256::from(parent.number + 1);
+            env.block.timestamp = U256::from(parent.timestamp + SIMULATED_BLOCK_TIME);
+            // Without validation, the calls are executed with a zero base fee and any gas price.
+            env.block.basefee = if validation { base_fee } else { U256::ZERO };
+            env.cfg.disable_base_fee = !validation;
+            env.cfg.disable_nonce_check = !validation;
+
+            if let Some(block_overrides) = block_overrides {
+                apply_block_overrides(block_overrides, &mut db.0, &mut env.block);
+            }
+            if env.block.number <= U256::from(parent.number) {
+                return Err(RethEthApiError::InvalidParams("block numbers must be in order".to_string()).into());
+            }
+            if env.block.timestamp <= U256::from(parent.timestamp) {
+                return Err(RethEthApiError::InvalidParams("block timestamps must be in order".to_string()).into());
+            }
+            if let Some(state_overrides) = state_overrides {
+                apply_state_overrides(state_overrides, &mut db.0)?;
+            }
+
+            let block =
+                simulate_block(&mut db, &env, calls, parent.hash, &mut remaining_gas, trace_transfers, validation)?;
+            parent = block.inner.header.clone();
+            blocks.push(block);
+        }
+
+        Ok(blocks)
+    }
+}
+
+/// Executes the calls in the block of the environment, and commits their state changes. The gas
+/// used by the calls is deducted from the remaining gas of the request.
+fn simulate_block<P: EthereumProvider + Send + Sync>(
+    db: &mut EthCacheDatabase<P>,
+    env: &EnvWithHandlerCfg,
+    calls: Vec<TransactionRequest>,
+    parent_hash: B256,
+    remaining_request_gas: &mut u64,
+    trace_transfers: bool,
+    validation: bool,
+) -> EthApiResult<SimulatedBlock<ExtendedBlock>> {
+    let block_gas_limit: u64 = env.block.gas_limit.saturating_to();
+    let chain_id = env.cfg.chain_id;
+    let evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+
+    let mut gas_used = 0;
+    let mut results = Vec::with_capacity(calls.len());
+    for request in calls {
+        let remaining_block_gas = block_gas_limit.saturating_sub(gas_used);
+        if let Some(gas) = request.gas.filter(|gas| validation && *gas > remaining_block_gas) {
+            return Err(TransactionError::ExceedsBlockGasLimit(gas.into(), block_gas_limit.into()).into());
+        }
+        if *remaining_request_gas == 0 {
+            return Err(RethEthApiError::InvalidParams(format!(
+                "the calls exceed the gas cap of {CALL_REQUEST_GAS_LIMIT}"
+            ))
+            .into());
+        }
+        let remaining_gas = remaining_block_gas.min(*remaining_request_gas);
+
+        let mut env = env.clone();
+        env.tx = tx_env_from_request(&request, remaining_gas, chain_id);
+        // The gas of the request is also capped for the calls with an explicit gas.
+        env.tx.gas_limit = env.tx.gas_limit.min(remaining_gas);
+
+        // The transfer inspector adds the ERC-7528 logs of the native transfers to the result.
+        let inspector = TransferInspector::new(false).with_logs(trace_transfers);
+        let result = evm_config
+            .evm_with_env_and_inspector(&mut db.0, env, inspector)
+            .transact_commit()
+            .map_err(|err| TransactionError::Call(err.into()))?;
+
+        gas_used += result.gas_used();
+        *remaining_request_gas = remaining_request_gas.saturating_sub(result.gas_used());
+        results.push(sim_call_result(result));
+    }
+
+    Ok(simulated_block(&env.block, parent_hash, gas_used, results))
+}
+
+/// Returns the simulated block, and sets the position of the logs of its calls.
+fn simulated_block(
+    block_env: &BlockEnv,
+    parent_hash: B256,
+    gas_used: u64,
+    mut calls: Vec<SimCallResult>,
+) -> SimulatedBlock<ExtendedBlock> {
+    let mut logs_bloom = Bloom::default();
+    calls.iter().flat_map(|call| &call.logs).for_each(|log| logs_bloom.accrue_log(&log.inner));
+
+    let mut header = Header {
+        parent_hash,
+        miner: block_env.coinbase,
+        logs_bloom,
+        number: block_env.number.saturating_to(),
+        gas_limit: block_env.gas_limit.saturating_to(),
+        gas_used,
+        timestamp: block_env.timestamp.saturating_to(),
+        mix_hash: block_env.prevrandao,
+        nonce: Some(B64::ZERO),
+        base_fee_per_gas: Some(block_env.basefee.saturating_to()),
+        ..Default::default()
+    };
+    // The header is built from valid values, its conversion can't fail.
+    header.hash =
+        reth_primitives::Header::try_from(header.clone()).map(|header| header.hash_slow()).unwrap_or_default();
+
+    let mut log_index = 0;
+    for (transaction_index, call) in (0..).zip(&mut calls) {
+        for log in &mut call.logs {
+            log.block_hash = Some(header.hash);
+            log.block_number = Some(header.number);
+            log.block_timestamp = Some(header.timestamp);
+            log.transaction_index = Some(transaction_index);
+            log.log_index = Some(log_index);
+            log_index += 1;
+        }
+    }
+
+    // The simulated calls are unsigned, the block doesn't include transactions.
+    let block = Block { header, transactions: BlockTransactions::Hashes(Vec::new()), ..Default::default() };
+    SimulatedBlock { inner: WithOtherFields::new(block), calls }
+}
+
+/// Returns the simulation result of the execution result of a call.
+fn sim_call_result(result: ExecutionResult) -> SimCallResult {
+    match result {
+        ExecutionResult::Success { output, gas_used, logs, .. } => SimCallResult {
+            return_data: output.into_data(),
+            logs: logs.into_iter().map(|inner| Log { inner, ..Default::default() }).collect(),
+            gas_used,
+            status: true,
+            error: None,
+        },
+        ExecutionResult::Revert { output, gas_used } => SimCallResult {
+            error: Some(SimulateError {
+                code: EthRpcErrorCode::ExecutionError as i32,
+                message: ExecutionError::from(EvmError::Other(output.clone())).to_string(),
+            }),
+            return_data: output,
+            logs: Vec::new(),
+            gas_used,
+            status: false,
+        },
+        ExecutionResult::Halt { reason, gas_used } => SimCallResult {
+            return_data: Default::default(),
+            logs: Vec::new(),
+            gas_used,
+            status: false,
+            error: Some(SimulateError { code: VM_ERROR_CODE, message: format!("execution halted: {reason:?}") }),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bytes, LogData};
+    use reth_revm::primitives::{HaltReason, Output, SuccessReason};
+
+    #[test]
+    fn test_sim_call_result() {
+        // Given
+        let success = ExecutionResult::Success {
+            reason: SuccessReason::Return,
+            gas_used: 21000,
+            gas_refunded: 0,
+            logs: vec![alloy_primitives::Log { address: Address::ZERO, data: LogData::default() }],
+            output: Output::Call(Bytes::from_static(&[1])),
+        };
+        let halt = ExecutionResult::Halt { reason: HaltReason::OpcodeNotFound, gas_used: 30000 };
+
+        // When
+        let success = sim_call_result(success);
+        let halt = sim_call_result(halt);
+
+        // Then
+        assert!(success.status);
+        assert_eq!(success.return_data, Bytes::from_static(&[1]));
+        assert_eq!(success.logs.len(), 1);
+        assert_eq!(success.gas_used, 21000);
+        assert!(success.error.is_none());
+
+        assert!(!halt.status);
+        assert_eq!(halt.gas_used, 30000);
+        assert_eq!(halt.error.map(|error| error.code), Some(VM_ERROR_CODE));
+    }
+
+    #[test]
+    fn test_simulated_block_log_positions() {
+        // Given
+        let block_env = BlockEnv { number: U256::from(2), timestamp: U256::from(24), ..Default::default() };
+        let call = |logs: usize| SimCallResult {
+            return_data: Bytes::new(),
+            logs: vec![Log::default(); logs],
+            gas_used: 21000,
+            status: true,
+            error: None,
+        };
+        let parent_hash = B256::repeat_byte(1);
+
+        // When
+        let block = simulated_block(&block_env, parent_hash, 42000, vec![call(2), call(1)]);
+
+        // Then
+        let header = &block.inner.header;
+        assert_eq!(header.number, 2);
+        assert_eq!(header.timestamp, 24);
+        assert_eq!(header.gas_used, 42000);
+        assert_eq!(header.parent_hash, parent_hash);
+        assert_ne!(header.hash, B256::ZERO);
+
+        let positions = block
+            .calls
+            .iter()
+            .flat_map(|call| &call.logs)
+            .map(|log| (log.transaction_index, log.log_index, log.block_hash))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (Some(0), Some(0), Some(header.hash)),
+                (Some(0), Some(1), Some(header.hash)),
+                (Some(1), Some(2), Some(header.hash)),
+            ]
+        );
+    }
+}
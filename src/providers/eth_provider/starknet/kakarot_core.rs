@@ -1,14 +1,13 @@
 #![allow(clippy::too_many_arguments)]
 
-use crate::into_via_wrapper;
+use crate::{config::Config, into_via_wrapper};
 use alloy_primitives::{Address, B256};
 use cainome::rs::abigen_legacy;
-use dotenvy::dotenv;
 use starknet::{
     core::{types::Felt, utils::get_contract_address},
     macros::selector,
 };
-use std::{str::FromStr, sync::LazyLock};
+use std::sync::LazyLock;
 // Contract ABIs
 
 pub mod account_contract {
@@ -33,19 +32,21 @@ pub mod core {
     }
 }
 
-fn env_var_to_field_element(var_name: &str) -> Felt {
-    dotenv().ok();
-    let env_var = std::env::var(var_name).unwrap_or_else(|_| panic!("Missing environment variable {var_name}"));
-
-    Felt::from_str(&env_var).unwrap_or_else(|_| panic!("Invalid hex string for {var_name}"))
-}
-
 /// Kakarot address
-pub static KAKAROT_ADDRESS: LazyLock<Felt> = LazyLock::new(|| env_var_to_field_element("KAKAROT_ADDRESS"));
+pub static KAKAROT_ADDRESS: LazyLock<Felt> = LazyLock::new(|| {
+    Config::current()
+        .expect("failed to read the configuration")
+        .kakarot_address
+        .expect("kakarot_address is validated at startup")
+});
 
 /// Uninitialized account class hash
-pub static UNINITIALIZED_ACCOUNT_CLASS_HASH: LazyLock<Felt> =
-    LazyLock::new(|| env_var_to_field_element("UNINITIALIZED_ACCOUNT_CLASS_HASH"));
+pub static UNINITIALIZED_ACCOUNT_CLASS_HASH: LazyLock<Felt> = LazyLock::new(|| {
+    Config::current()
+        .expect("failed to read the configuration")
+        .uninitialized_account_class_hash
+        .expect("uninitialized_account_class_hash is validated at startup")
+});
 
 /// Ethereum send transaction selector
 pub static ETH_SEND_TRANSACTION: LazyLock<Felt> = LazyLock::new(|| selector!("eth_send_transaction"));
@@ -55,20 +56,24 @@ pub static EXECUTE_FROM_OUTSIDE: LazyLock<Felt> = LazyLock::new(|| selector!("ex
 
 /// Maximum number of felts in calldata
 pub static MAX_FELTS_IN_CALLDATA: LazyLock<usize> = LazyLock::new(|| {
-    usize::from_str(
-        &std::env::var("MAX_FELTS_IN_CALLDATA")
-            .unwrap_or_else(|_| panic!("Missing environment variable MAX_FELTS_IN_CALLDATA")),
-    )
-    .expect("Failed to parse MAX_FELTS_IN_CALLDATA")
+    Config::current()
+        .expect("failed to read the configuration")
+        .max_felts_in_calldata
+        .expect("max_felts_in_calldata is validated at startup")
+});
+
+/// White listed pre EIP-155 transaction hashes
+static WHITE_LISTED_EIP_155_TRANSACTION_HASHES: LazyLock<Vec<B256>> = LazyLock::new(|| {
+    Config::current()
+        .expect("failed to read the configuration")
+        .white_listed_eip_155_transaction_hashes
+        .clone()
+        .unwrap_or_default()
 });
 
-pub fn get_white_listed_eip_155_transaction_hashes() -> Vec<B256> {
-    std::env::var("WHITE_LISTED_EIP_155_TRANSACTION_HASHES")
-        .unwrap_or_else(|_| panic!("Missing environment variable WHITE_LISTED_EIP_155_TRANSACTION_HASHES"))
-        .replace(' ', "")
-        .split(',')
-        .map(|hash| B256::from_str(hash).unwrap())
-        .collect()
+/// Returns the white listed pre EIP-155 transaction hashes, which are rejected at startup if invalid.
+pub fn get_white_listed_eip_155_transaction_hashes() -> &'static [B256] {
+    &WHITE_LISTED_EIP_155_TRANSACTION_HASHES
 }
 
 // Kakarot utils
@@ -1,32 +1,54 @@
 use super::{
-    database::state::{EthCacheDatabase, EthDatabase},
-    error::{EthApiError, ExecutionError, TransactionError},
+    constant::CALL_REQUEST_GAS_LIMIT,
+    database::{
+        ethereum::EthereumBlockStore,
+        state::{EthCacheDatabase, EthDatabase},
+        types::header::ExtendedBlock,
+    },
+    error::{EthApiError, EthereumDataFormatError, EvmError, ExecutionError, TransactionError},
     starknet::kakarot_core::{account_contract::AccountContractReader, starknet_address},
-    utils::{contract_not_found, entrypoint_not_found, split_u256},
+    state_cache::{StateKey, StateSlot},
+    utils::{contract_not_found, entrypoint_not_found, split_u256, tx_env_from_request},
 };
 use crate::{
     into_via_wrapper,
-    providers::eth_provider::{
-        provider::{EthApiResult, EthDataProvider},
-        BlockProvider, ChainProvider,
+    models::bundle::{CallBundle, CallBundleResponse},
+    providers::{
+        eth_provider::{
+            provider::{EthApiResult, EthDataProvider},
+            BlockProvider, ChainProvider, TransactionProvider,
+        },
+        sn_provider::storage_proof::get_storage_proof,
     },
 };
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{keccak256, Address, Bytes, TxKind, B256, U256};
 use alloy_rpc_types::{
     serde_helpers::JsonStorageKey,
-    state::{EvmOverrides, StateOverride},
-    BlockOverrides, TransactionRequest,
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+    AccessListResult, BlockOverrides, Bundle, EIP1186AccountProofResponse, EIP1186StorageProof, EthCallResponse,
+    Header, StateContext, TransactionRequest,
 };
+use alloy_serde::WithOtherFields;
 use async_trait::async_trait;
 use auto_impl::auto_impl;
+use futures::future::try_join_all;
 use mongodb::bson::doc;
 use num_traits::cast::ToPrimitive;
 use reth_evm_ethereum::EthEvmConfig;
 use reth_node_api::ConfigureEvm;
-use reth_revm::db::CacheDB;
-use reth_rpc_eth_types::error::ensure_success;
-use starknet::core::utils::get_storage_var_address;
+use reth_revm::{
+    db::CacheDB,
+    primitives::{BlockEnv, CfgEnv, Env, EnvWithHandlerCfg, ExecutionResult, HandlerCfg, SpecId},
+    DatabaseRef,
+};
+use reth_rpc_eth_types::{
+    error::ensure_success,
+    revm_utils::{apply_block_overrides, apply_state_overrides, get_precompiles},
+};
+use revm_inspectors::access_list::AccessListInspector;
+use starknet::core::{types::Felt, utils::get_storage_var_address};
 use std::sync::Arc;
 use tracing::Instrument;
 
@@ -55,6 +77,47 @@ pub trait StateProvider: ChainProvider + BlockProvider {
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> EthApiResult<Bytes>;
+
+    /// Returns the results of the simulation of the blocks of calls on top of the state of the
+    /// given block, each call being executed on top of the state changes of the previous ones.
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<Vec<SimulatedBlock<ExtendedBlock>>>;
+
+    /// Returns the output or the error of each transaction request of the bundle, executed one
+    /// after the other on top of the state context.
+    async fn call_many(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<Vec<EthCallResponse>>;
+
+    /// Returns the result of each signed transaction of the bundle, executed one after the other
+    /// on top of the state block, and the payments of the bundle to the coinbase.
+    async fn call_bundle(&self, bundle: CallBundle) -> EthApiResult<CallBundleResponse>;
+
+    /// Returns the EIP-1186 proof of the account and its storage keys.
+    ///
+    /// The state of Kakarot lives in Starknet tries, so `account_proof` and the `proof` of each
+    /// storage proof are always empty: the Starknet storage proof of the account contract is only
+    /// carried in the `starknetStorageProof` field.
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<B256>,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<WithOtherFields<EIP1186AccountProofResponse>>;
+
+    /// Returns the access list accessed by the request and the gas used when executing the
+    /// request with this access list.
+    async fn create_access_list(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<AccessListResult>;
 }
 
 #[async_trait]
@@ -65,8 +128,16 @@ where
     async fn balance(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<U256> {
         // Convert the optional Ethereum block ID to a Starknet block ID.
         let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        let key = StateKey::new(address, StateSlot::Balance, &starknet_block_id);
         // Get the balance of the address at the given block ID.
-        self.starknet_provider().balance_at(starknet_address(address), starknet_block_id).await.map_err(Into::into)
+        self.state_cache()
+            .get_or_fetch(key, || async move {
+                self.starknet_provider()
+                    .balance_at(starknet_address(address), starknet_block_id)
+                    .await
+                    .map_err(Into::into)
+            })
+            .await
     }
 
     async fn storage_at(
@@ -76,44 +147,56 @@ where
         block_id: Option<BlockId>,
     ) -> EthApiResult<B256> {
         let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        let key = StateKey::new(address, StateSlot::Storage(U256::from(index.0)), &starknet_block_id);
 
-        let address = starknet_address(address);
-        let contract = AccountContractReader::new(address, self.starknet_provider_inner());
+        let storage = self
+            .state_cache()
+            .get_or_fetch(key, || async move {
+                let address = starknet_address(address);
+                let contract = AccountContractReader::new(address, self.starknet_provider_inner());
 
-        let keys = split_u256(index.0);
-        let storage_address = get_storage_var_address("Account_storage", &keys).expect("Storage var name is not ASCII");
+                let keys = split_u256(index.0);
+                let storage_address =
+                    get_storage_var_address("Account_storage", &keys).expect("Storage var name is not ASCII");
 
-        let span = tracing::span!(tracing::Level::INFO, "sn::storage");
-        let maybe_storage =
-            contract.storage(&storage_address).block_id(starknet_block_id).call().instrument(span).await;
+                let span = tracing::span!(tracing::Level::INFO, "sn::storage");
+                let maybe_storage =
+                    contract.storage(&storage_address).block_id(starknet_block_id).call().instrument(span).await;
 
-        if contract_not_found(&maybe_storage) || entrypoint_not_found(&maybe_storage) {
-            return Ok(U256::ZERO.into());
-        }
+                if contract_not_found(&maybe_storage) || entrypoint_not_found(&maybe_storage) {
+                    return Ok(U256::ZERO);
+                }
 
-        let storage = maybe_storage.map_err(ExecutionError::from)?.value;
-        let low: U256 = into_via_wrapper!(storage.low);
-        let high: U256 = into_via_wrapper!(storage.high);
-        let storage: U256 = low + (high << 128);
+                let storage = maybe_storage.map_err(ExecutionError::from)?.value;
+                let low: U256 = into_via_wrapper!(storage.low);
+                let high: U256 = into_via_wrapper!(storage.high);
+                Ok(low + (high << 128))
+            })
+            .await?;
 
         Ok(storage.into())
     }
 
     async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<Bytes> {
         let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        let key = StateKey::new(address, StateSlot::Code, &starknet_block_id);
 
-        let address = starknet_address(address);
-        let account_contract = AccountContractReader::new(address, self.starknet_provider_inner());
-        let span = tracing::span!(tracing::Level::INFO, "sn::code");
-        let bytecode = account_contract.bytecode().block_id(starknet_block_id).call().instrument(span).await;
+        self.code_cache()
+            .get_or_fetch(key, || async move {
+                let address = starknet_address(address);
+                let account_contract = AccountContractReader::new(address, self.starknet_provider_inner());
+                let span = tracing::span!(tracing::Level::INFO, "sn::code");
+                let bytecode = account_contract.bytecode().block_id(starknet_block_id).call().instrument(span).await;
 
-        if contract_not_found(&bytecode) || entrypoint_not_found(&bytecode) {
-            return Ok(Bytes::default());
-        }
+                if contract_not_found(&bytecode) || entrypoint_not_found(&bytecode) {
+                    return Ok(Bytes::default());
+                }
 
-        let bytecode = bytecode.map_err(ExecutionError::from)?.bytecode.0;
+                let bytecode = bytecode.map_err(ExecutionError::from)?.bytecode.0;
 
-        Ok(Bytes::from(bytecode.into_iter().filter_map(|x| x.to_u8()).collect::<Vec<_>>()))
+                Ok(Bytes::from(bytecode.into_iter().filter_map(|x| x.to_u8()).collect::<Vec<_>>()))
+            })
+            .await
     }
 
     async fn call(
@@ -123,55 +206,29 @@ where
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> EthApiResult<Bytes> {
-        // Create the EVM overrides from the state and block overrides.
-        let evm_overrides = EvmOverrides::new(state_overrides, block_overrides);
-
-        // Check if either state_overrides or block_overrides is present.
-        if evm_overrides.has_state() || evm_overrides.has_block() {
-            // // Create the configuration environment with the chain ID.
-            // let cfg_env = CfgEnv::default().with_chain_id(self.chain_id().await?.unwrap_or_default().to());
-
-            // // Retrieve the block header details.
-            // let Header { number, timestamp, miner, base_fee_per_gas, difficulty, .. } =
-            //     self.header(&block_id.unwrap_or_default()).await?.unwrap_or_default();
-
-            // // Create the block environment with the retrieved header details and transaction request.
-            // let block_env = BlockEnv {
-            //     number: U256::from(number),
-            //     timestamp: U256::from(timestamp),
-            //     gas_limit: U256::from(request.gas.unwrap_or_default()),
-            //     coinbase: miner,
-            //     basefee: U256::from(base_fee_per_gas.unwrap_or_default()),
-            //     prevrandao: Some(B256::from_slice(&difficulty.to_be_bytes::<32>()[..])),
-            //     ..Default::default()
-            // };
-
-            // // Combine the configuration environment with the handler configuration.
-            // let cfg_env_with_handler_cfg =
-            //     CfgEnvWithHandlerCfg { cfg_env, handler_cfg: HandlerCfg::new(SpecId::CANCUN) };
-
-            // Create a snapshot of the Ethereum database using the block ID.
-            let db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id.unwrap_or_default())));
-
-            // TODO: no more prepare_call_env function (was put in trait, to be discussed)
-            // // Prepare the call environment with the transaction request, gas limit, and overrides.
-            // let env = prepare_call_env(
-            //     cfg_env_with_handler_cfg,
-            //     block_env,
-            //     request.clone(),
-            //     request.gas.unwrap_or_default().try_into().expect("Gas limit is too large"),
-            //     &mut db.0,
-            //     evm_overrides,
-            // )?;
-
-            // Execute the transaction using the configured EVM asynchronously.
+        if state_overrides.is_some() || block_overrides.is_some() {
+            let block_id = block_id.unwrap_or_default();
+            let mut env = self.call_env(&request, block_id).await?;
+            let mut db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id)));
+
+            if let Some(block_overrides) = block_overrides {
+                apply_block_overrides(*block_overrides, &mut db.0, &mut env.env.block);
+                // Requests without gas limit use the gas limit of the overridden block.
+                if request.gas.is_none() {
+                    env.env.tx.gas_limit = env.env.block.gas_limit.saturating_to();
+                }
+            }
+            if let Some(state_overrides) = state_overrides {
+                apply_state_overrides(state_overrides, &mut db.0)?;
+            }
+            // The gas of the call is capped as for the calls without overrides, whatever the block gas limit.
+            env.env.tx.gas_limit = env.env.tx.gas_limit.min(CALL_REQUEST_GAS_LIMIT);
+
             let res = EthEvmConfig::new(Arc::new(Default::default()))
-            // TODO: We should not use default here, to be modified to activate the correct EVM config
-                .evm_with_env(db.0, Default::default())
+                .evm_with_env(db.0, env)
                 .transact()
-                .map_err(|err| <TransactionError as Into<EthApiError>>::into(TransactionError::Call(err.into())))?;
+                .map_err(|err| TransactionError::Call(err.into()))?;
 
-            // Ensure the transaction was successful and return the result.
             return Ok(ensure_success(res.result)?);
         }
 
@@ -179,4 +236,180 @@ where
         let output = self.call_inner(request, block_id).await?;
         Ok(Bytes::from(output.0.into_iter().filter_map(|x| x.to_u8()).collect::<Vec<_>>()))
     }
+
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<Vec<SimulatedBlock<ExtendedBlock>>> {
+        self.simulate(payload, block_id).await
+    }
+
+    async fn call_many(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_overrides: Option<StateOverride>,
+    ) -> EthApiResult<Vec<EthCallResponse>> {
+        self.execute_bundle(bundle, state_context, state_overrides).await
+    }
+
+    async fn call_bundle(&self, bundle: CallBundle) -> EthApiResult<CallBundleResponse> {
+        self.execute_signed_bundle(bundle).await
+    }
+
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<B256>,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<WithOtherFields<EIP1186AccountProofResponse>> {
+        let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        let contract_address = starknet_address(address);
+
+        // Each EVM storage slot is a Uint256 stored in the `Account_storage` storage var,
+        // its low and high parts use two consecutive Starknet storage slots.
+        let storage_keys = keys
+            .iter()
+            .flat_map(|key| {
+                let storage_address = get_storage_var_address("Account_storage", &split_u256(*key))
+                    .expect("Storage var name is not ASCII");
+                [storage_address, storage_address + Felt::ONE]
+            })
+            .collect();
+
+        let span = tracing::span!(tracing::Level::INFO, "sn::storage_proof");
+        let proof =
+            get_storage_proof(self.storage_proof_url().clone(), starknet_block_id, contract_address, storage_keys)
+                .instrument(span)
+                .await?;
+
+        let (balance, nonce, code) = tokio::try_join!(
+            self.balance(address, block_id),
+            self.transaction_count(address, block_id),
+            self.get_code(address, block_id)
+        )?;
+        let storage_values =
+            try_join_all(keys.iter().map(|key| self.storage_at(address, JsonStorageKey(*key), block_id))).await?;
+
+        let storage_proof = keys
+            .into_iter()
+            .zip(storage_values)
+            .map(|(key, value)| EIP1186StorageProof {
+                key: JsonStorageKey(key),
+                value: U256::from_be_bytes(value.0),
+                proof: Vec::new(),
+            })
+            .collect();
+        let storage_hash = proof
+            .contracts_proof
+            .contract_leaves_data
+            .first()
+            .and_then(|leaf| leaf.storage_root)
+            .map(|root| B256::from(root.to_bytes_be()))
+            .unwrap_or_default();
+
+        let mut response = WithOtherFields::new(EIP1186AccountProofResponse {
+            address,
+            balance,
+            code_hash: keccak256(code),
+            nonce: nonce.to(),
+            storage_hash,
+            // The account and storage tries are Starknet tries, the proof can't be expressed with Ethereum nodes.
+            account_proof: Vec::new(),
+            storage_proof,
+        });
+        response.other.insert(
+            "starknetStorageProof".to_string(),
+            serde_json::to_value(proof).map_err(|_| EthereumDataFormatError::CustomError("storage proof"))?,
+        );
+
+        Ok(response)
+    }
+
+    async fn create_access_list(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+    ) -> EthApiResult<AccessListResult> {
+        let block_id = block_id.unwrap_or_default();
+        let mut env = self.call_env(&request, block_id).await?;
+        let db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self, block_id)));
+
+        // The sender, the recipient and the precompiles are always warm, they are only listed with the
+        // storage slots they access.
+        let from = env.tx.caller;
+        let to = match env.tx.transact_to {
+            TxKind::Call(to) => to,
+            TxKind::Create => from.create(db.0.basic_ref(from)?.unwrap_or_default().nonce),
+        };
+        let precompiles = get_precompiles(env.handler_cfg.spec_id);
+        let mut inspector = AccessListInspector::new(request.access_list.unwrap_or_default(), from, to, precompiles);
+
+        let evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+        evm_config
+            .evm_with_env_and_inspector(db.0.clone(), env.clone(), &mut inspector)
+            .transact()
+            .map_err(|err| TransactionError::Call(err.into()))?;
+        let access_list = inspector.into_access_list();
+
+        // Warm accesses are cheaper, execute the request again with the access list to get the gas used.
+        env.tx.access_list.clone_from(&access_list.0);
+        let result =
+            evm_config.evm_with_env(db.0, env).transact().map_err(|err| TransactionError::Call(err.into()))?.result;
+
+        let error = match &result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output, .. } => {
+                Some(ExecutionError::from(EvmError::Other(output.clone())).to_string())
+            }
+            ExecutionResult::Halt { reason, .. } => Some(format!("execution halted: {reason:?}")),
+        };
+
+        Ok(AccessListResult { access_list, gas_used: U256::from(result.gas_used()), error })
+    }
+}
+
+impl<SP> EthDataProvider<SP>
+where
+    SP: starknet::providers::Provider + Send + Sync,
+{
+    /// Returns the environment to execute the request on top of the state of the given block.
+    pub(crate) async fn call_env(
+        &self,
+        request: &TransactionRequest,
+        block_id: BlockId,
+    ) -> EthApiResult<EnvWithHandlerCfg> {
+        let header = self.call_header(block_id).await?;
+        let chain_id = self.chain_id().await?.unwrap_or_default().to();
+        Ok(call_env_at(&header, request, chain_id))
+    }
+
+    /// Returns the header of the block which state the requests are executed on top of.
+    pub(crate) async fn call_header(&self, block_id: BlockId) -> EthApiResult<Header> {
+        let block_hash_or_number = self.block_id_into_block_number_or_hash(block_id).await?;
+        self.database().header(block_hash_or_number).await?.ok_or(EthApiError::UnknownBlock(block_hash_or_number))
+    }
+}
+
+/// Returns the environment to execute the request in the block of the given header.
+pub(crate) fn call_env_at(header: &Header, request: &TransactionRequest, chain_id: u64) -> EnvWithHandlerCfg {
+    let cfg = CfgEnv::default().with_chain_id(chain_id);
+
+    // Requests without fees are executed with a zero base fee, as done by Geth.
+    let has_fees = request.gas_price.is_some() || request.max_fee_per_gas.is_some();
+    let basefee = if has_fees { U256::from(header.base_fee_per_gas.unwrap_or_default()) } else { U256::ZERO };
+
+    let block = BlockEnv {
+        number: U256::from(header.number),
+        timestamp: U256::from(header.timestamp),
+        gas_limit: U256::from(header.gas_limit),
+        coinbase: header.miner,
+        basefee,
+        prevrandao: Some(B256::from_slice(&header.difficulty.to_be_bytes::<32>()[..])),
+        ..Default::default()
+    };
+    let tx = tx_env_from_request(request, header.gas_limit, chain_id);
+
+    EnvWithHandlerCfg::new(Box::new(Env { cfg, block, tx }), HandlerCfg::new(SpecId::CANCUN))
 }
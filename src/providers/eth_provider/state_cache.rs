@@ -0,0 +1,213 @@
+use super::provider::EthApiResult;
+use alloy_primitives::{Address, U256};
+use schnellru::{ByLength, LruMap};
+use starknet::core::types::{BlockId, Felt};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tokio::sync::OnceCell;
+
+/// A value of the state of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateSlot {
+    Balance,
+    Nonce,
+    Code,
+    Storage(U256),
+}
+
+/// A Starknet block which state can't change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StateBlock {
+    Number(u64),
+    Hash(Felt),
+}
+
+/// Key of a value of the state of an account at a Starknet block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StateKey {
+    address: Address,
+    slot: StateSlot,
+    block: StateBlock,
+}
+
+impl StateKey {
+    /// Returns the key of the value at the given block, or `None` if the block is designated by a
+    /// tag, as the state at the pending or latest block changes.
+    pub const fn new(address: Address, slot: StateSlot, block_id: &BlockId) -> Option<Self> {
+        let block = match block_id {
+            BlockId::Number(number) => StateBlock::Number(*number),
+            BlockId::Hash(hash) => StateBlock::Hash(*hash),
+            BlockId::Tag(_) => return None,
+        };
+        Some(Self { address, slot, block })
+    }
+}
+
+/// Size bounded LRU cache of state values read from Starknet, which coalesces the concurrent
+/// reads of the same value into a single request.
+///
+/// Clones share the same cache.
+#[derive(Clone)]
+pub struct StateCache<V> {
+    values: Arc<Mutex<LruMap<StateKey, V>>>,
+    in_flight: Arc<Mutex<HashMap<StateKey, Arc<OnceCell<V>>>>>,
+    max_entries: u32,
+}
+
+impl<V> std::fmt::Debug for StateCache<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateCache").field("max_entries", &self.max_entries).finish_non_exhaustive()
+    }
+}
+
+impl<V: Clone> StateCache<V> {
+    /// Creates a cache holding up to `max_entries` values, the cache is disabled if 0.
+    pub fn new(max_entries: u32) -> Self {
+        Self {
+            values: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_entries)))),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            max_entries,
+        }
+    }
+
+    /// Returns the cached value, or fetches it. Values without key are always fetched. Concurrent
+    /// calls for the same key wait for the first fetch, and fetch again only if it failed.
+    pub async fn get_or_fetch<F, Fut>(&self, key: Option<StateKey>, fetch: F) -> EthApiResult<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = EthApiResult<V>>,
+    {
+        let Some(key) = key.filter(|_| self.max_entries > 0) else {
+            return fetch().await;
+        };
+
+        let cached = self.values().get(&key).cloned();
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+
+        let cell = Arc::clone(self.in_flight().entry(key).or_default());
+        // Removes the pending fetch once the value is cached, or if this future is dropped.
+        let _in_flight = InFlightGuard { in_flight: &self.in_flight, key, cell: Arc::clone(&cell) };
+        let value = cell.get_or_try_init(fetch).await.cloned();
+
+        if let Ok(value) = &value {
+            self.values().insert(key, value.clone());
+        }
+
+        value
+    }
+
+    fn values(&self) -> MutexGuard<'_, LruMap<StateKey, V>> {
+        self.values.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn in_flight(&self) -> MutexGuard<'_, HashMap<StateKey, Arc<OnceCell<V>>>> {
+        self.in_flight.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Removes the pending fetch of a key from the in flight fetches when dropped, unless it was
+/// already replaced by another fetch.
+struct InFlightGuard<'a, V> {
+    in_flight: &'a Mutex<HashMap<StateKey, Arc<OnceCell<V>>>>,
+    key: StateKey,
+    cell: Arc<OnceCell<V>>,
+}
+
+impl<V> Drop for InFlightGuard<'_, V> {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        if in_flight.get(&self.key).is_some_and(|pending| Arc::ptr_eq(pending, &self.cell)) {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::types::BlockTag;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_state_key_of_tagged_block() {
+        // Given
+        let address = Address::ZERO;
+
+        // When
+        let pending = StateKey::new(address, StateSlot::Balance, &BlockId::Tag(BlockTag::Pending));
+        let latest = StateKey::new(address, StateSlot::Balance, &BlockId::Tag(BlockTag::Latest));
+        let number = StateKey::new(address, StateSlot::Balance, &BlockId::Number(1));
+
+        // Then
+        assert!(pending.is_none());
+        assert!(latest.is_none());
+        assert!(number.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_coalesces_requests() {
+        // Given
+        let cache = StateCache::<U256>::new(10);
+        let key = StateKey::new(Address::ZERO, StateSlot::Nonce, &BlockId::Number(1));
+        let fetches = &AtomicUsize::new(0);
+        let fetch = move || async move {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(U256::from(1))
+        };
+
+        // When
+        let (first, second) = tokio::join!(cache.get_or_fetch(key, fetch), cache.get_or_fetch(key, fetch));
+        let cached = cache.get_or_fetch(key, fetch).await;
+
+        // Then
+        assert_eq!(first.unwrap(), U256::from(1));
+        assert_eq!(second.unwrap(), U256::from(1));
+        assert_eq!(cached.unwrap(), U256::from(1));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(cache.in_flight().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_dropped_before_completion() {
+        // Given
+        let cache = StateCache::<U256>::new(10);
+        let key = StateKey::new(Address::ZERO, StateSlot::Nonce, &BlockId::Number(1));
+        let fetch = || std::future::pending::<EthApiResult<U256>>();
+
+        // When
+        let mut pending = Box::pin(cache.get_or_fetch(key, fetch));
+        let polled = futures::poll!(pending.as_mut());
+        let in_flight = cache.in_flight().len();
+        drop(pending);
+
+        // Then
+        assert!(polled.is_pending());
+        assert_eq!(in_flight, 1);
+        assert!(cache.in_flight().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_without_key() {
+        // Given
+        let cache = StateCache::<U256>::new(10);
+        let key = StateKey::new(Address::ZERO, StateSlot::Nonce, &BlockId::Tag(BlockTag::Pending));
+        let fetches = &AtomicUsize::new(0);
+        let fetch = move || async move {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(U256::from(1))
+        };
+
+        // When
+        cache.get_or_fetch(key, fetch).await.unwrap();
+        cache.get_or_fetch(key, fetch).await.unwrap();
+
+        // Then
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
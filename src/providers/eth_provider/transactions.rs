@@ -5,6 +5,7 @@ use super::{
     },
     error::ExecutionError,
     starknet::kakarot_core::{account_contract::AccountContractReader, starknet_address},
+    state_cache::{StateKey, StateSlot},
     utils::{contract_not_found, entrypoint_not_found},
 };
 use crate::{
@@ -84,17 +85,23 @@ where
 
     async fn transaction_count(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<U256> {
         let starknet_block_id = self.to_starknet_block_id(block_id).await?;
+        let key = StateKey::new(address, StateSlot::Nonce, &starknet_block_id);
 
-        let address = starknet_address(address);
-        let account_contract = AccountContractReader::new(address, self.starknet_provider_inner());
-        let span = tracing::span!(tracing::Level::INFO, "sn::kkrt_nonce");
-        let maybe_nonce = account_contract.get_nonce().block_id(starknet_block_id).call().instrument(span).await;
+        self.state_cache()
+            .get_or_fetch(key, || async move {
+                let address = starknet_address(address);
+                let account_contract = AccountContractReader::new(address, self.starknet_provider_inner());
+                let span = tracing::span!(tracing::Level::INFO, "sn::kkrt_nonce");
+                let maybe_nonce =
+                    account_contract.get_nonce().block_id(starknet_block_id).call().instrument(span).await;
 
-        if contract_not_found(&maybe_nonce) || entrypoint_not_found(&maybe_nonce) {
-            return Ok(U256::ZERO);
-        }
-        let nonce = maybe_nonce.map_err(ExecutionError::from)?.nonce;
+                if contract_not_found(&maybe_nonce) || entrypoint_not_found(&maybe_nonce) {
+                    return Ok(U256::ZERO);
+                }
+                let nonce = maybe_nonce.map_err(ExecutionError::from)?.nonce;
 
-        Ok(into_via_wrapper!(nonce))
+                Ok(into_via_wrapper!(nonce))
+            })
+            .await
     }
 }
@@ -1,5 +1,7 @@
-use alloy_primitives::{U128, U256};
+use alloy_primitives::{TxKind, U128, U256};
+use alloy_rpc_types::TransactionRequest;
 use cainome::cairo_serde::Error;
+use reth_revm::primitives::TxEnv;
 use starknet::{
     core::types::{ContractErrorData, StarknetError},
     providers::ProviderError,
@@ -63,9 +65,35 @@ pub(crate) fn entrypoint_not_found<T>(err: &Result<T, Error>) -> bool {
     }
 }
 
+/// Converts a transaction request into a transaction environment. Missing fields default
+/// to a zero gas price and the provided gas limit.
+pub(crate) fn tx_env_from_request(request: &TransactionRequest, gas_limit: u64, chain_id: u64) -> TxEnv {
+    let (gas_price, gas_priority_fee) = match request.max_fee_per_gas {
+        Some(max_fee_per_gas) => (max_fee_per_gas, request.max_priority_fee_per_gas),
+        None => (request.gas_price.unwrap_or_default(), None),
+    };
+
+    TxEnv {
+        caller: request.from.unwrap_or_default(),
+        gas_limit: request.gas.unwrap_or(gas_limit),
+        gas_price: U256::from(gas_price),
+        gas_priority_fee: gas_priority_fee.map(U256::from),
+        transact_to: request.to.unwrap_or(TxKind::Create),
+        value: request.value.unwrap_or_default(),
+        data: request.input.input().cloned().unwrap_or_default(),
+        nonce: request.nonce,
+        chain_id: Some(chain_id),
+        access_list: request.access_list.clone().map(|access_list| access_list.0).unwrap_or_default(),
+        blob_hashes: request.blob_versioned_hashes.clone().unwrap_or_default(),
+        max_fee_per_blob_gas: request.max_fee_per_blob_gas.map(U256::from),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::{Address, Bytes};
     use proptest::prelude::*;
     use std::str::FromStr;
 
@@ -95,4 +123,27 @@ mod tests {
 
         assert!(class_hash_not_declared::<()>(&Err(err)));
     }
+
+    #[test]
+    fn test_tx_env_from_request() {
+        // Given
+        let request = TransactionRequest::default()
+            .from(Address::repeat_byte(1))
+            .to(Address::repeat_byte(2))
+            .max_fee_per_gas(10)
+            .max_priority_fee_per_gas(1)
+            .input(Bytes::from_static(&[1, 2, 3]).into());
+
+        // When
+        let tx_env = tx_env_from_request(&request, 1_000, 1);
+
+        // Then
+        assert_eq!(tx_env.caller, Address::repeat_byte(1));
+        assert_eq!(tx_env.transact_to, TxKind::Call(Address::repeat_byte(2)));
+        assert_eq!(tx_env.gas_limit, 1_000);
+        assert_eq!(tx_env.gas_price, U256::from(10));
+        assert_eq!(tx_env.gas_priority_fee, Some(U256::from(1)));
+        assert_eq!(tx_env.data, Bytes::from_static(&[1, 2, 3]));
+        assert_eq!(tx_env.chain_id, Some(1));
+    }
 }
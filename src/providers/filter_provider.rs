@@ -0,0 +1,281 @@
+use crate::{
+    client::EthClient,
+    providers::eth_provider::{
+        database::ethereum::EthereumBlockStore, error::EthApiError, provider::EthApiResult, BlockProvider, LogProvider,
+    },
+};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{B256, U64};
+use alloy_rpc_types::{Filter, FilterBlockOption, FilterChanges};
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reth_transaction_pool::{TransactionListenerKind, TransactionPool};
+use starknet::providers::Provider;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc::Receiver, Mutex},
+    time::Instant,
+};
+
+/// Duration after which a filter that hasn't been polled is evicted.
+pub const STALE_FILTER_TTL: Duration = Duration::from_secs(300);
+
+#[async_trait]
+#[auto_impl(Arc, &)]
+pub trait FilterProvider {
+    /// Installs a log filter and returns its id.
+    async fn new_filter(&self, filter: Filter) -> EthApiResult<U64>;
+    /// Installs a filter notifying of new blocks and returns its id.
+    async fn new_block_filter(&self) -> EthApiResult<U64>;
+    /// Installs a filter notifying of new pending transactions and returns its id.
+    async fn new_pending_transaction_filter(&self) -> EthApiResult<U64>;
+    /// Uninstalls the filter with the given id. Returns true if the filter existed.
+    async fn uninstall_filter(&self, id: U64) -> EthApiResult<bool>;
+    /// Returns the changes for the filter since the last poll.
+    async fn filter_changes(&self, id: U64) -> EthApiResult<FilterChanges>;
+    /// Returns all the logs matching the log filter.
+    async fn filter_logs(&self, id: U64) -> EthApiResult<FilterChanges>;
+}
+
+/// The kind of an installed filter.
+#[derive(Debug)]
+enum FilterKind {
+    /// Log filter.
+    Log(Box<Filter>),
+    /// New block filter.
+    Block,
+    /// New pending transaction filter, fed by the mempool.
+    PendingTransaction(Receiver<B256>),
+}
+
+/// A filter installed in the registry.
+#[derive(Debug)]
+struct ActiveFilter {
+    /// The kind of the filter.
+    kind: FilterKind,
+    /// The last block number returned by the filter.
+    block: u64,
+    /// The last time the filter was polled.
+    last_poll: Instant,
+}
+
+type Filters = Arc<Mutex<HashMap<U64, ActiveFilter>>>;
+
+/// In-process registry for the `eth_newFilter` family of methods.
+///
+/// Block and log filters keep a cursor over the database's `headers` and `logs`
+/// collections, pending transaction filters listen to the mempool. Filters which
+/// aren't polled for [`STALE_FILTER_TTL`] are evicted.
+#[derive(Debug)]
+pub struct FilterDataProvider<SP: Provider + Send + Sync> {
+    eth_client: Arc<EthClient<SP>>,
+    filters: Filters,
+}
+
+impl<SP> FilterDataProvider<SP>
+where
+    SP: Provider + Clone + Send + Sync + 'static,
+{
+    /// Creates the filter registry and spawns the task evicting stale filters.
+    pub fn new(eth_client: Arc<EthClient<SP>>) -> Self {
+        let filters = Filters::default();
+        evict_stale_filters(Arc::downgrade(&filters), STALE_FILTER_TTL);
+        Self { eth_client, filters }
+    }
+
+    /// Inserts the filter in the registry under a new random id.
+    async fn install_filter(&self, kind: FilterKind) -> EthApiResult<U64> {
+        let block = self.eth_client.eth_provider().block_number().await?.to();
+
+        // Ids are random so that clients can't poll or uninstall filters they didn't create.
+        // Use `StdRng` instead of `ThreadRng` as it is `Send`
+        let mut rng = StdRng::from_entropy();
+
+        let mut filters = self.filters.lock().await;
+        let id = loop {
+            let id = U64::from(rng.gen::<u64>());
+            if !filters.contains_key(&id) {
+                break id;
+            }
+        };
+        filters.insert(id, ActiveFilter { kind, block, last_poll: Instant::now() });
+
+        Ok(id)
+    }
+
+    /// Returns the hashes of the blocks in the range `[from, to]`, ordered by block number.
+    async fn block_hashes(&self, from: u64, to: u64) -> EthApiResult<Vec<B256>> {
+        let headers = self.eth_client.eth_provider().database().headers(from, to).await?;
+
+        // A zero hash corresponds to the pending block, which isn't a new block yet.
+        Ok(headers.into_iter().map(|header| header.hash).filter(|hash| !hash.is_zero()).collect())
+    }
+
+    /// Returns the logs matching the filter in the range `[from, to]`, intersected with
+    /// the block range of the filter itself.
+    async fn logs_in_range(&self, filter: Filter, from: u64, to: u64) -> EthApiResult<FilterChanges> {
+        let (from, to) = match filter.block_option {
+            FilterBlockOption::Range { from_block, to_block } => (
+                from_block.and_then(|block| block.as_number()).map_or(from, |block| block.max(from)),
+                to_block.and_then(|block| block.as_number()).map_or(to, |block| block.min(to)),
+            ),
+            // The block hash is resolved by the log provider.
+            FilterBlockOption::AtBlockHash(_) => (from, to),
+        };
+        if from > to {
+            return Ok(FilterChanges::Empty);
+        }
+
+        let changes = match filter.block_option {
+            FilterBlockOption::AtBlockHash(_) => self.eth_client.eth_provider().get_logs(filter).await?,
+            FilterBlockOption::Range { .. } => {
+                self.eth_client
+                    .eth_provider()
+                    .get_logs(filter.from_block(BlockNumberOrTag::Number(from)).to_block(BlockNumberOrTag::Number(to)))
+                    .await?
+            }
+        };
+
+        // Block hash filters only return logs from blocks which weren't returned yet.
+        Ok(match changes {
+            FilterChanges::Logs(logs) => FilterChanges::Logs(
+                logs.into_iter().filter(|log| log.block_number.is_some_and(|n| n >= from && n <= to)).collect(),
+            ),
+            changes => changes,
+        })
+    }
+}
+
+#[async_trait]
+impl<SP> FilterProvider for FilterDataProvider<SP>
+where
+    SP: Provider + Clone + Send + Sync + 'static,
+{
+    async fn new_filter(&self, filter: Filter) -> EthApiResult<U64> {
+        self.install_filter(FilterKind::Log(Box::new(filter))).await
+    }
+
+    async fn new_block_filter(&self) -> EthApiResult<U64> {
+        self.install_filter(FilterKind::Block).await
+    }
+
+    async fn new_pending_transaction_filter(&self) -> EthApiResult<U64> {
+        let listener = self.eth_client.mempool().pending_transactions_listener_for(TransactionListenerKind::All);
+        self.install_filter(FilterKind::PendingTransaction(listener)).await
+    }
+
+    async fn uninstall_filter(&self, id: U64) -> EthApiResult<bool> {
+        Ok(self.filters.lock().await.remove(&id).is_some())
+    }
+
+    async fn filter_changes(&self, id: U64) -> EthApiResult<FilterChanges> {
+        let current_block: u64 = self.eth_client.eth_provider().block_number().await?.to();
+
+        // The database is queried once the lock is released, the cursor of the filter is only
+        // moved forward once the changes are fetched so that a failed query can be retried.
+        let (start_block, filter) = {
+            let mut filters = self.filters.lock().await;
+            let active = filters.get_mut(&id).ok_or(EthApiError::FilterNotFound(id))?;
+            active.last_poll = Instant::now();
+
+            let start_block = active.block.saturating_add(1);
+
+            match &mut active.kind {
+                FilterKind::PendingTransaction(receiver) => {
+                    let mut hashes = Vec::new();
+                    while let Ok(hash) = receiver.try_recv() {
+                        hashes.push(hash);
+                    }
+                    return Ok(FilterChanges::Hashes(hashes));
+                }
+                FilterKind::Block => (start_block, None),
+                FilterKind::Log(filter) => (start_block, Some(filter.clone())),
+            }
+        };
+
+        if start_block > current_block {
+            return Ok(FilterChanges::Empty);
+        }
+
+        let changes = match filter {
+            None => FilterChanges::Hashes(self.block_hashes(start_block, current_block).await?),
+            Some(filter) => self.logs_in_range(*filter, start_block, current_block).await?,
+        };
+
+        if let Some(active) = self.filters.lock().await.get_mut(&id) {
+            active.block = active.block.max(current_block);
+        }
+
+        Ok(changes)
+    }
+
+    async fn filter_logs(&self, id: U64) -> EthApiResult<FilterChanges> {
+        let filter = {
+            let mut filters = self.filters.lock().await;
+            let active = filters.get_mut(&id).ok_or(EthApiError::FilterNotFound(id))?;
+            active.last_poll = Instant::now();
+
+            match &active.kind {
+                FilterKind::Log(filter) => filter.clone(),
+                _ => return Err(EthApiError::FilterNotFound(id)),
+            }
+        };
+
+        self.eth_client.eth_provider().get_logs(*filter).await
+    }
+}
+
+/// Spawns a task which periodically removes the filters that haven't been polled
+/// during the last `ttl`. The task stops once the registry is dropped.
+fn evict_stale_filters(filters: Weak<Mutex<HashMap<U64, ActiveFilter>>>, ttl: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ttl);
+        loop {
+            interval.tick().await;
+
+            let Some(filters) = filters.upgrade() else {
+                return;
+            };
+            remove_stale_filters(&mut *filters.lock().await, Instant::now(), ttl);
+        }
+    });
+}
+
+/// Removes the filters which were last polled more than `ttl` before `now`.
+fn remove_stale_filters(filters: &mut HashMap<U64, ActiveFilter>, now: Instant, ttl: Duration) {
+    filters.retain(|id, filter| {
+        let is_stale = now.duration_since(filter.last_poll) > ttl;
+        if is_stale {
+            tracing::trace!(target: "rpc::eth::filter", ?id, "evicting stale filter");
+        }
+        !is_stale
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_stale_filters() {
+        // Given
+        let ttl = Duration::from_secs(10);
+        let now = Instant::now();
+        let mut filters = HashMap::from([
+            (U64::from(1), ActiveFilter { kind: FilterKind::Block, block: 0, last_poll: now }),
+            (U64::from(2), ActiveFilter { kind: FilterKind::Block, block: 0, last_poll: now + ttl }),
+        ]);
+
+        // When
+        remove_stale_filters(&mut filters, now + ttl + Duration::from_secs(1), ttl);
+
+        // Then
+        assert!(!filters.contains_key(&U64::from(1)));
+        assert!(filters.contains_key(&U64::from(2)));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    into_via_try_wrapper, into_via_wrapper,
+    models::kakarot::{AccountClassHashes, AccountStatus},
+    providers::eth_provider::{
+        database::ethereum::EthereumTransactionStore,
+        error::{ExecutionError, KakarotError},
+        provider::{EthApiResult, EthDataProvider},
+        starknet::kakarot_core::{
+            account_contract::AccountContractReader, core::KakarotCoreReader, starknet_address, KAKAROT_ADDRESS,
+        },
+        utils::{contract_not_found, entrypoint_not_found},
+    },
+};
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types::BlockId;
+use async_trait::async_trait;
+use auto_impl::auto_impl;
+use starknet::{
+    core::types::{Felt, StarknetError},
+    providers::{Provider, ProviderError},
+};
+use tracing::Instrument;
+
+#[async_trait]
+#[auto_impl(Arc, &)]
+pub trait KakarotProvider {
+    /// Returns the hash of the Ethereum transaction relayed by the Starknet transaction.
+    async fn eth_transaction_hash(&self, starknet_hash: Felt) -> EthApiResult<Option<B256>>;
+    /// Returns the hash of the Starknet transaction which relayed the Ethereum transaction.
+    async fn starknet_transaction_hash(&self, eth_hash: B256) -> EthApiResult<Option<Felt>>;
+    /// Returns the Starknet address of the account of the EVM address.
+    fn starknet_address(&self, address: Address) -> Felt;
+    /// Returns whether the Starknet account of the EVM address is deployed and initialized.
+    async fn account_status(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<AccountStatus>;
+    /// Returns the base fee set in the Kakarot contract.
+    async fn base_fee(&self, block_id: Option<BlockId>) -> EthApiResult<U256>;
+    /// Returns the coinbase set in the Kakarot contract.
+    async fn coinbase(&self, block_id: Option<BlockId>) -> EthApiResult<Address>;
+    /// Returns the class hashes of the accounts deployed by the Kakarot contract.
+    async fn account_class_hashes(&self, block_id: Option<BlockId>) -> EthApiResult<AccountClassHashes>;
+}
+
+#[derive(Debug, Clone)]
+pub struct KakarotDataProvider<SP: Provider + Send + Sync> {
+    eth_provider: EthDataProvider<SP>,
+}
+
+impl<SP: Provider + Send + Sync> KakarotDataProvider<SP> {
+    pub const fn new(eth_provider: EthDataProvider<SP>) -> Self {
+        Self { eth_provider }
+    }
+
+    fn kakarot_contract(&self) -> KakarotCoreReader<&SP> {
+        KakarotCoreReader::new(*KAKAROT_ADDRESS, self.eth_provider.starknet_provider_inner())
+    }
+}
+
+#[async_trait]
+impl<SP: Provider + Send + Sync + 'static> KakarotProvider for KakarotDataProvider<SP> {
+    async fn eth_transaction_hash(&self, starknet_hash: Felt) -> EthApiResult<Option<B256>> {
+        self.eth_provider.database().eth_transaction_hash(&starknet_hash).await
+    }
+
+    async fn starknet_transaction_hash(&self, eth_hash: B256) -> EthApiResult<Option<Felt>> {
+        self.eth_provider.database().starknet_transaction_hash(&eth_hash).await
+    }
+
+    fn starknet_address(&self, address: Address) -> Felt {
+        starknet_address(address)
+    }
+
+    async fn account_status(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<AccountStatus> {
+        let starknet_block_id = self.eth_provider.to_starknet_block_id(block_id).await?;
+        let starknet_address = starknet_address(address);
+
+        let span = tracing::span!(tracing::Level::INFO, "sn::class_hash_at");
+        let class_hash = match self
+            .eth_provider
+            .starknet_provider_inner()
+            .get_class_hash_at(starknet_block_id, starknet_address)
+            .instrument(span)
+            .await
+        {
+            Ok(class_hash) => Some(class_hash),
+            Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => None,
+            Err(err) => return Err(KakarotError::from(err).into()),
+        };
+
+        let initialized = if class_hash.is_some() {
+            let account_contract =
+                AccountContractReader::new(starknet_address, self.eth_provider.starknet_provider_inner());
+            let span = tracing::span!(tracing::Level::INFO, "sn::is_initialized");
+            let maybe_initialized =
+                account_contract.is_initialized().block_id(starknet_block_id).call().instrument(span).await;
+
+            if contract_not_found(&maybe_initialized) || entrypoint_not_found(&maybe_initialized) {
+                false
+            } else {
+                maybe_initialized.map_err(ExecutionError::from)?.is_initialized != Felt::ZERO
+            }
+        } else {
+            false
+        };
+
+        Ok(AccountStatus {
+            evm_address: address,
+            starknet_address,
+            deployed: class_hash.is_some(),
+            initialized,
+            class_hash,
+        })
+    }
+
+    async fn base_fee(&self, block_id: Option<BlockId>) -> EthApiResult<U256> {
+        let starknet_block_id = self.eth_provider.to_starknet_block_id(block_id).await?;
+        let span = tracing::span!(tracing::Level::INFO, "sn::base_fee");
+        let base_fee = self
+            .kakarot_contract()
+            .get_base_fee()
+            .block_id(starknet_block_id)
+            .call()
+            .instrument(span)
+            .await
+            .map_err(ExecutionError::from)?
+            .base_fee;
+        Ok(into_via_wrapper!(base_fee))
+    }
+
+    async fn coinbase(&self, block_id: Option<BlockId>) -> EthApiResult<Address> {
+        let starknet_block_id = self.eth_provider.to_starknet_block_id(block_id).await?;
+        let span = tracing::span!(tracing::Level::INFO, "sn::coinbase");
+        let coinbase = self
+            .kakarot_contract()
+            .get_coinbase()
+            .block_id(starknet_block_id)
+            .call()
+            .instrument(span)
+            .await
+            .map_err(ExecutionError::from)?
+            .coinbase;
+        Ok(into_via_try_wrapper!(coinbase)?)
+    }
+
+    async fn account_class_hashes(&self, block_id: Option<BlockId>) -> EthApiResult<AccountClassHashes> {
+        let starknet_block_id = self.eth_provider.to_starknet_block_id(block_id).await?;
+        let kakarot_contract = self.kakarot_contract();
+
+        let span = tracing::span!(tracing::Level::INFO, "sn::account_contract_class_hash");
+        let account_contract_class_hash = kakarot_contract
+            .get_account_contract_class_hash()
+            .block_id(starknet_block_id)
+            .call()
+            .instrument(span)
+            .await
+            .map_err(ExecutionError::from)?
+            .account_contract_class_hash;
+
+        let span = tracing::span!(tracing::Level::INFO, "sn::uninitialized_account_class_hash");
+        let uninitialized_account_class_hash = kakarot_contract
+            .get_uninitialized_account_class_hash()
+            .block_id(starknet_block_id)
+            .call()
+            .instrument(span)
+            .await
+            .map_err(ExecutionError::from)?
+            .uninitialized_account_class_hash;
+
+        Ok(AccountClassHashes { account_contract_class_hash, uninitialized_account_class_hash })
+    }
+}
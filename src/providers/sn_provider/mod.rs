@@ -1,3 +1,4 @@
 pub mod starknet_provider;
+pub mod storage_proof;
 
 pub use starknet_provider::StarknetProvider;
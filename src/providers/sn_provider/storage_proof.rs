@@ -0,0 +1,148 @@
+use crate::providers::eth_provider::error::KakarotError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use starknet::core::types::{BlockId, Felt};
+use std::sync::LazyLock;
+use url::Url;
+
+/// HTTP client used to query the storage proofs, sharing its connection pool between requests.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+/// Response of the Starknet `starknet_getStorageProof` method.
+/// See <https://github.com/starkware-libs/starknet-specs/blob/v0.8.0/api/starknet_api_openrpc.json>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageProof {
+    /// Proof of the requested class hashes in the classes trie.
+    pub classes_proof: Vec<NodeWithHash>,
+    /// Proof of the requested contracts in the contracts trie.
+    pub contracts_proof: ContractsProof,
+    /// Proof of the requested storage keys, one per requested contract.
+    pub contracts_storage_proofs: Vec<Vec<NodeWithHash>>,
+    /// Roots of the tries at the requested block.
+    pub global_roots: GlobalRoots,
+}
+
+/// A node of a Merkle-Patricia trie, along with its hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeWithHash {
+    pub node_hash: Felt,
+    pub node: MerkleNode,
+}
+
+/// A node of a Starknet Merkle-Patricia trie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MerkleNode {
+    /// Node with two children.
+    Binary { left: Felt, right: Felt },
+    /// Node compressing a path of `length` bits to its child.
+    Edge { path: Felt, length: u64, child: Felt },
+}
+
+/// Proof of the contracts in the contracts trie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractsProof {
+    /// The nodes of the proof.
+    pub nodes: Vec<NodeWithHash>,
+    /// The leaves of the requested contracts, in the order of the request.
+    pub contract_leaves_data: Vec<ContractLeafData>,
+}
+
+/// The data of a contract leaf in the contracts trie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractLeafData {
+    pub nonce: Felt,
+    pub class_hash: Felt,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_root: Option<Felt>,
+}
+
+/// The roots of the Starknet tries and the hash of the block they belong to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobalRoots {
+    pub contracts_tree_root: Felt,
+    pub classes_tree_root: Felt,
+    pub block_hash: Felt,
+}
+
+/// A JSON-RPC response, either a result or an error.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+/// Fetches the proof of the storage keys of the contract at the given block.
+///
+/// `starknet_getStorageProof` isn't part of the Starknet specification supported by the
+/// Starknet provider, so the request is sent directly to the node.
+pub async fn get_storage_proof(
+    url: Url,
+    block_id: BlockId,
+    contract_address: Felt,
+    storage_keys: Vec<Felt>,
+) -> Result<StorageProof, KakarotError> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "starknet_getStorageProof",
+        "params": {
+            "block_id": block_id,
+            "contract_addresses": [contract_address],
+            "contracts_storage_keys": [{ "contract_address": contract_address, "storage_keys": storage_keys }],
+        },
+    });
+
+    let response: JsonRpcResponse<StorageProof> = HTTP_CLIENT
+        .post(url)
+        .json(&request)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|err| KakarotError::StorageProof(err.to_string()))?
+        .json()
+        .await
+        .map_err(|err| KakarotError::StorageProof(err.to_string()))?;
+
+    match response {
+        JsonRpcResponse { result: Some(proof), .. } => Ok(proof),
+        JsonRpcResponse { error, .. } => Err(KakarotError::StorageProof(error.unwrap_or_default().to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_storage_proof() {
+        // Given
+        let response = json!({
+            "classes_proof": [],
+            "contracts_proof": {
+                "nodes": [
+                    { "node_hash": "0x1", "node": { "left": "0x2", "right": "0x3" } },
+                    { "node_hash": "0x2", "node": { "path": "0x4", "length": 2, "child": "0x5" } }
+                ],
+                "contract_leaves_data": [{ "nonce": "0x0", "class_hash": "0x6", "storage_root": "0x7" }]
+            },
+            "contracts_storage_proofs": [[]],
+            "global_roots": { "contracts_tree_root": "0x1", "classes_tree_root": "0x8", "block_hash": "0x9" }
+        });
+
+        // When
+        let proof: StorageProof = serde_json::from_value(response).unwrap();
+
+        // Then
+        assert_eq!(
+            proof.contracts_proof.nodes[0].node,
+            MerkleNode::Binary { left: Felt::from(2), right: Felt::from(3) }
+        );
+        assert_eq!(
+            proof.contracts_proof.nodes[1].node,
+            MerkleNode::Edge { path: Felt::from(4), length: 2, child: Felt::from(5) }
+        );
+        assert_eq!(proof.contracts_proof.contract_leaves_data[0].storage_root, Some(Felt::from(7)));
+        assert_eq!(proof.global_roots.block_hash, Felt::from(9));
+    }
+}
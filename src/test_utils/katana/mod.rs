@@ -233,7 +233,7 @@ impl<'a> Katana {
     }
 
     pub fn eth_provider(&self) -> Arc<EthDataProvider<Arc<JsonRpcClient<HttpTransport>>>> {
-        Arc::new(self.eoa.eth_client.eth_provider().clone())
+        Arc::new(self.eoa.eth_client.eth_provider().inner().clone())
     }
 
     pub fn starknet_provider(&self) -> Arc<JsonRpcClient<HttpTransport>> {
@@ -292,6 +292,26 @@ impl<'a> Katana {
             .expect("Failed to insert logs into the database");
     }
 
+    /// Adds a log to the database, with its block number padded like the ones of the indexer.
+    pub async fn add_log_to_database(&self, log: Log) {
+        let provider = self.eth_provider();
+        let database = provider.database();
+        let padded_block_number = format_hex(log.block_number.unwrap_or_default(), U64_HEX_STRING_LEN);
+
+        let mut log_doc = bson::to_document(&StoredLog { log }).expect("Failed to serialize StoredLog to BSON");
+        log_doc
+            .get_document_mut("log")
+            .expect("Failed to get the log document")
+            .insert("blockNumber", padded_block_number);
+
+        database
+            .inner()
+            .collection::<Document>(StoredLog::collection_name())
+            .insert_one(log_doc)
+            .await
+            .expect("Failed to insert log into the database");
+    }
+
     /// Adds transactions to the database along with a corresponding header.
     pub async fn add_transactions_with_header_to_database(&self, txs: Vec<ExtendedTransaction>, header: Header) {
         let provider = self.eth_provider();
@@ -48,7 +48,7 @@ mock! {
 
     #[async_trait]
     impl GasProvider for EthereumProviderStruct {
-        async fn estimate_gas(&self, call: TransactionRequest, block_id: Option<BlockId>) -> EthApiResult<U256>;
+        async fn estimate_gas(&self, call: TransactionRequest, block_id: Option<BlockId>, state_overrides: Option<alloy_rpc_types::state::StateOverride>) -> EthApiResult<U256>;
 
         async fn fee_history(&self, block_count: U64, newest_block: BlockNumberOrTag, reward_percentiles: Option<Vec<f64>>) -> EthApiResult<alloy_rpc_types::FeeHistory>;
 
@@ -76,6 +76,16 @@ mock! {
         async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> EthApiResult<Bytes>;
 
         async fn call(&self, request: TransactionRequest, block_id: Option<BlockId>, state_overrides: Option<alloy_rpc_types::state::StateOverride>, block_overrides: Option<Box<alloy_rpc_types::BlockOverrides>>) -> EthApiResult<Bytes>;
+
+        async fn simulate_v1(&self, payload: alloy_rpc_types::simulate::SimulatePayload, block_id: Option<BlockId>) -> EthApiResult<Vec<alloy_rpc_types::simulate::SimulatedBlock<ExtendedBlock>>>;
+
+        async fn call_many(&self, bundle: alloy_rpc_types::Bundle, state_context: Option<alloy_rpc_types::StateContext>, state_overrides: Option<alloy_rpc_types::state::StateOverride>) -> EthApiResult<Vec<alloy_rpc_types::EthCallResponse>>;
+
+        async fn call_bundle(&self, bundle: crate::models::bundle::CallBundle) -> EthApiResult<crate::models::bundle::CallBundleResponse>;
+
+        async fn get_proof(&self, address: Address, keys: Vec<B256>, block_id: Option<BlockId>) -> EthApiResult<alloy_serde::WithOtherFields<alloy_rpc_types::EIP1186AccountProofResponse>>;
+
+        async fn create_access_list(&self, request: TransactionRequest, block_id: Option<BlockId>) -> EthApiResult<alloy_rpc_types::AccessListResult>;
     }
 
     #[async_trait]
@@ -10,7 +10,7 @@ use crate::providers::eth_provider::{
         CollectionName, Database,
     },
 };
-use alloy_primitives::{B256, U256};
+use alloy_primitives::{Bloom, B256, U256};
 use alloy_rpc_types::Transaction;
 use arbitrary::Arbitrary;
 use mongodb::{
@@ -151,9 +151,24 @@ impl MongoFuzzer {
 
             self.logs.push(StoredLog { log });
         }
+
+        self.update_logs_blooms();
+
         Ok(())
     }
 
+    /// Sets the logs bloom of the headers to the bloom of the logs of their block, as the logs
+    /// queries select the blocks to search from the blooms.
+    fn update_logs_blooms(&mut self) {
+        for header in &mut self.headers {
+            let mut bloom = Bloom::default();
+            for stored_log in self.logs.iter().filter(|stored_log| stored_log.log.block_number == Some(header.number)) {
+                bloom.accrue_log(&stored_log.log.inner);
+            }
+            header.header.logs_bloom = bloom;
+        }
+    }
+
     /// Gets the highest block number in the transactions collection.
     pub fn max_block_number(&self) -> u64 {
         self.headers.iter().map(|header| header.number).max().unwrap_or_default()
@@ -203,6 +218,8 @@ impl MongoFuzzer {
 
         self.headers.push(header_with_base_fee);
 
+        self.update_logs_blooms();
+
         Ok(())
     }
 
@@ -1,9 +1,16 @@
 use super::katana::Katana;
-use crate::eth_rpc::{config::RPCConfig, rpc::KakarotRpcModuleBuilder, run_server};
+use crate::{
+    eth_rpc::{
+        config::{AdminRPCConfig, RPCConfig, RequestLimitsConfig, RpcModulesConfig},
+        rpc::{KakarotRpcModule, KakarotRpcModuleBuilder},
+        run_admin_server, run_server, RpcServerMetrics,
+    },
+    prometheus_handler::Registry,
+};
 use jsonrpsee::server::ServerHandle;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::net::SocketAddr;
+use std::{collections::HashSet, net::SocketAddr, path::PathBuf};
 
 /// Sets up the environment for Kakarot RPC integration tests by deploying the Kakarot contracts
 /// and starting the Kakarot RPC server.
@@ -58,14 +65,49 @@ pub async fn start_kakarot_rpc_server(katana: &Katana) -> Result<(SocketAddr, Se
     let eth_client = katana.eth_client();
     Ok(run_server(
         KakarotRpcModuleBuilder::new(eth_client.into()).rpc_module()?,
-        #[cfg(feature = "testing")]
         RPCConfig::new_test_config_from_port(rand::random()),
-        #[cfg(not(feature = "testing"))]
-        RPCConfig::from_port(3030),
+        RpcServerMetrics::new(&Registry::new())?,
     )
     .await?)
 }
 
+/// Starts the admin RPC server of Kakarot, serving the debug, trace and txpool modules to clients
+/// authenticated with the JWT secret stored at the given path. The secret is created if missing.
+pub async fn start_kakarot_admin_rpc_server(
+    katana: &Katana,
+    jwt_secret_path: PathBuf,
+) -> Result<(SocketAddr, ServerHandle), eyre::Report> {
+    let eth_client = katana.eth_client();
+    let modules = HashSet::from([KakarotRpcModule::Debug, KakarotRpcModule::Trace, KakarotRpcModule::Txpool]);
+    let config = AdminRPCConfig {
+        socket_addr: RPCConfig::new_test_config_from_port(rand::random()).socket_addr,
+        jwt_secret_path,
+        modules: RpcModulesConfig { modules: Some(modules), ..Default::default() },
+    };
+
+    let admin_rpc_module = KakarotRpcModuleBuilder::new(eth_client.into()).rpc_module_with_config(&config.modules)?;
+    Ok(run_admin_server(
+        admin_rpc_module,
+        config,
+        RequestLimitsConfig::DEFAULT,
+        RpcServerMetrics::new(&Registry::new())?,
+    )
+    .await?)
+}
+
+/// Sends the JSON-RPC request to the Kakarot RPC server listening on the port, and returns the response.
+pub async fn send_request(port: u16, body: String) -> Value {
+    let res = reqwest::Client::new()
+        .post(format!("http://localhost:{port}"))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to send request");
+    serde_json::from_str(&res.text().await.expect("Failed to get response body"))
+        .expect("Failed to deserialize response body")
+}
+
 /// Represents a builder for creating JSON-RPC requests.
 /// Taken from <https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc-builder/tests/it/http.rs>
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -196,6 +196,15 @@ impl<P: EthereumProvider + Send + Sync + Clone> TracerBuilder<P, Pinned> {
         Ok(Tracer { transactions, env, db, tracing_options })
     }
 
+    /// Builds the tracer for calls executed on top of the state of the block, instead
+    /// of replaying the transactions of the block.
+    pub fn build_for_call(self) -> Tracer<P> {
+        let env = self.init_env_with_handler_config();
+        let db = EthCacheDatabase(CacheDB::new(EthDatabase::new(self.eth_provider, self.block.header.hash.into())));
+
+        Tracer { transactions: Vec::new(), env, db, tracing_options: self.tracing_options }
+    }
+
     /// Init an `EnvWithHandlerCfg`.
     fn init_env_with_handler_config(&self) -> EnvWithHandlerCfg {
         let env = Box::new(self.init_env_with_block_env());
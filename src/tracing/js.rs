@@ -0,0 +1,262 @@
+use super::{Tracer, TracerResult};
+use crate::providers::eth_provider::{
+    database::state::EthCacheDatabase, error::TransactionError, provider::EthereumProvider,
+};
+use alloy_primitives::{Address, Log, U256};
+use alloy_rpc_types::TransactionInfo;
+use alloy_rpc_types_trace::geth::GethTrace;
+use eyre::eyre;
+use reth_revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, InstructionResult, Interpreter},
+    primitives::{EnvWithHandlerCfg, EvmState},
+    Database, EvmContext, Inspector,
+};
+use revm_inspectors::tracing::js::{JsInspector, TransactionContext};
+use std::time::{Duration, Instant};
+use tokio::{runtime::Handle, sync::Semaphore};
+
+/// Time limit of a JS tracer when no timeout is provided, as in Geth.
+const DEFAULT_JS_TRACER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum time limit of a JS tracer, regardless of the requested timeout.
+const MAX_JS_TRACER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time left to the tracing thread after the timeout, to halt the execution between two EVM
+/// steps and return the timeout error.
+const JS_TRACER_GRACE_PERIOD: Duration = Duration::from_millis(100);
+
+/// Maximum number of JS tracers running at the same time.
+const MAX_CONCURRENT_JS_TRACERS: usize = 4;
+
+/// Permits of the running JS tracers. A permit is only released once the JS engine returns, so
+/// tracers still running after their timeout count against the limit.
+static JS_TRACER_PERMITS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_JS_TRACERS);
+
+impl<P: EthereumProvider + Send + Sync + Clone + 'static> Tracer<P> {
+    /// Executes the environment with the JS tracer and returns the result of the tracer and the state.
+    ///
+    /// The execution is halted between two EVM steps once the timeout is reached, and the JS
+    /// hooks aren't called anymore. A call to the tracer can't be interrupted by the JS engine
+    /// before its loop iteration limit, so the tracer runs on a blocking thread which is abandoned
+    /// if it doesn't return in time. The number of tracers is capped, an abandoned tracer keeps its
+    /// permit until it returns.
+    pub(super) fn js_trace(
+        env: EnvWithHandlerCfg,
+        db: &EthCacheDatabase<P>,
+        transaction_info: TransactionInfo,
+        code: String,
+        config: serde_json::Value,
+        timeout: Option<&str>,
+    ) -> TracerResult<(GethTrace, EvmState)> {
+        let timeout = match timeout {
+            Some(timeout) => parse_timeout(timeout)
+                .ok_or_else(|| TransactionError::Tracing(eyre!("invalid timeout {timeout}").into()))?,
+            None => DEFAULT_JS_TRACER_TIMEOUT,
+        }
+        .min(MAX_JS_TRACER_TIMEOUT);
+
+        let permit = JS_TRACER_PERMITS
+            .try_acquire()
+            .map_err(|_| TransactionError::Tracing(eyre!("too many concurrent JS tracers").into()))?;
+        let deadline = Instant::now() + timeout;
+
+        let transaction_context = TransactionContext {
+            block_hash: transaction_info.block_hash,
+            tx_index: transaction_info.index.map(|index| index as usize),
+            tx_hash: transaction_info.hash,
+        };
+
+        // The JS inspector isn't `Send`, it is created on the tracing thread. The database of the
+        // tracing thread reads from Starknet through the runtime of the caller.
+        let db = db.clone();
+        let runtime = Handle::current();
+        let tracer = runtime.spawn_blocking(move || {
+            let _permit = permit;
+            Self::js_trace_with_deadline(env, &db, transaction_context, code, config, deadline)
+        });
+
+        // The tracer is synchronous, the caller waits for the tracing thread as the database does for Starknet.
+        match tokio::task::block_in_place(|| {
+            runtime.block_on(tokio::time::timeout(timeout + JS_TRACER_GRACE_PERIOD, tracer))
+        }) {
+            Ok(Ok(result)) => result,
+            Ok(Err(err)) => Err(TransactionError::Tracing(eyre!("JS tracer failed: {err}").into()).into()),
+            Err(_) => Err(TransactionError::Tracing(eyre!("execution timeout").into()).into()),
+        }
+    }
+
+    /// Executes the environment with the JS tracer, halting the execution once the deadline is reached.
+    fn js_trace_with_deadline(
+        env: EnvWithHandlerCfg,
+        db: &EthCacheDatabase<P>,
+        transaction_context: TransactionContext,
+        code: String,
+        config: serde_json::Value,
+        deadline: Instant,
+    ) -> TracerResult<(GethTrace, EvmState)> {
+        let inspector = JsInspector::with_transaction_context(code, config, transaction_context)
+            .map_err(|err| TransactionError::Tracing(eyre!("{err}").into()))?;
+        let mut inspector = DeadlineInspector { inner: inspector, deadline, timed_out: false };
+
+        let res = Self::inspect(env.clone(), db, &mut inspector)?;
+        if inspector.timed_out {
+            return Err(TransactionError::Tracing(eyre!("execution timeout").into()).into());
+        }
+
+        let state = res.state.clone();
+        let result = inspector
+            .inner
+            .json_result(res, &env, &db.0)
+            .map_err(|err| TransactionError::Tracing(eyre!("{err}").into()))?;
+
+        Ok((GethTrace::JS(result), state))
+    }
+}
+
+/// Parses a Geth timeout, a sequence of decimal numbers with a unit suffix, e.g. "300ms" or "1m30s".
+fn parse_timeout(timeout: &str) -> Option<Duration> {
+    const UNITS: [(&str, f64); 7] =
+        [("ns", 1e-9), ("us", 1e-6), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.), ("m", 60.), ("h", 3600.)];
+
+    let mut rest = timeout.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut seconds = 0.;
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+        let value: f64 = rest[..number_end].parse().ok()?;
+        rest = &rest[number_end..];
+
+        let unit_end = rest.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(rest.len());
+        let (_, unit) = UNITS.iter().find(|(unit, _)| *unit == &rest[..unit_end])?;
+        rest = &rest[unit_end..];
+
+        seconds += value * unit;
+    }
+
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Inspector halting the execution once the deadline is reached, and forwarding all the
+/// hooks to the inner inspector otherwise.
+#[derive(Debug)]
+struct DeadlineInspector<I> {
+    inner: I,
+    deadline: Instant,
+    timed_out: bool,
+}
+
+impl<I> DeadlineInspector<I> {
+    /// Returns true once the deadline is reached, after which the inner inspector isn't called anymore.
+    fn is_timed_out(&mut self) -> bool {
+        self.timed_out |= Instant::now() >= self.deadline;
+        self.timed_out
+    }
+}
+
+impl<DB: Database, I: Inspector<DB>> Inspector<DB> for DeadlineInspector<I> {
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if !self.is_timed_out() {
+            self.inner.initialize_interp(interp, context);
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if self.is_timed_out() {
+            interp.instruction_result = InstructionResult::OutOfGas;
+            return;
+        }
+        self.inner.step(interp, context);
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if !self.is_timed_out() {
+            self.inner.step_end(interp, context);
+        }
+    }
+
+    fn log(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>, log: &Log) {
+        if !self.is_timed_out() {
+            self.inner.log(interp, context, log);
+        }
+    }
+
+    fn call(&mut self, context: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        if self.is_timed_out() {
+            return None;
+        }
+        self.inner.call(context, inputs)
+    }
+
+    fn call_end(&mut self, context: &mut EvmContext<DB>, inputs: &CallInputs, outcome: CallOutcome) -> CallOutcome {
+        if self.is_timed_out() {
+            return outcome;
+        }
+        self.inner.call_end(context, inputs, outcome)
+    }
+
+    fn create(&mut self, context: &mut EvmContext<DB>, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        if self.is_timed_out() {
+            return None;
+        }
+        self.inner.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if self.is_timed_out() {
+            return outcome;
+        }
+        self.inner.create_end(context, inputs, outcome)
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if !self.is_timed_out() {
+            self.inner.selfdestruct(contract, target, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        // Given
+        let timeouts = ["300ms", "5s", "1m30s", "1.5h", "10µs"];
+
+        // When
+        let durations: Vec<_> = timeouts.iter().map(|timeout| parse_timeout(timeout)).collect();
+
+        // Then
+        assert_eq!(
+            durations,
+            vec![
+                Some(Duration::from_millis(300)),
+                Some(Duration::from_secs(5)),
+                Some(Duration::from_secs(90)),
+                Some(Duration::from_secs(5400)),
+                Some(Duration::from_micros(10)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_timeout() {
+        // Given
+        let timeouts = ["", "5", "s", "5x", "-5s"];
+
+        // When
+        let durations: Vec<_> = timeouts.iter().map(|timeout| parse_timeout(timeout)).collect();
+
+        // Then
+        assert!(durations.iter().all(Option::is_none));
+    }
+}
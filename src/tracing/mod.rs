@@ -1,37 +1,46 @@
 pub mod builder;
+#[cfg(feature = "js-tracer")]
+mod js;
 
 use crate::{
     providers::eth_provider::{
-        database::state::EthCacheDatabase,
+        database::state::{EthCacheDatabase, EthDatabase},
         error::{EthApiError, TransactionError},
         provider::EthereumProvider,
+        utils::tx_env_from_request,
     },
     tracing::builder::TracingOptions,
 };
-use alloy_primitives::{ruint::FromUintError, B256};
+use alloy_primitives::{ruint::FromUintError, Bytes, B256, U256};
 use alloy_rpc_types::{TransactionInfo, TransactionRequest};
 use alloy_rpc_types_trace::{
     geth::{
-        GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions,
-        GethTrace, TraceResult,
+        FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    parity::LocalizedTransactionTrace,
+    parity::{LocalizedTransactionTrace, TraceResults, TraceResultsWithTransactionHash, TraceType},
 };
 use alloy_serde::WithOtherFields;
 use eyre::eyre;
 use reth_evm_ethereum::EthEvmConfig;
 use reth_node_api::{ConfigureEvm, ConfigureEvmEnv};
 use reth_revm::{
-    primitives::{Env, EnvWithHandlerCfg},
-    DatabaseCommit,
+    db::CacheDB,
+    inspectors::NoOpInspector,
+    primitives::{Env, EnvWithHandlerCfg, EvmState, ResultAndState},
+    DatabaseCommit, GetInspector,
+};
+use reth_rpc_eth_types::revm_utils::{apply_block_overrides, apply_state_overrides};
+use revm_inspectors::tracing::{FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
 };
-use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
-use std::{collections::HashMap, sync::Arc};
 
 pub type TracerResult<T> = Result<T, EthApiError>;
 
 /// Represents the result of tracing a transaction.
-type TracingStateResult = TracerResult<(TracingResult, reth_revm::primitives::EvmState)>;
+type TracingStateResult = TracerResult<(TracingResult, EvmState)>;
 
 /// Representing the result of tracing transactions.
 #[derive(Clone, Debug)]
@@ -88,7 +97,7 @@ pub struct Tracer<P: EthereumProvider + Send + Sync> {
     tracing_options: TracingOptions,
 }
 
-impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
+impl<P: EthereumProvider + Send + Sync + Clone + 'static> Tracer<P> {
     /// Traces the transaction with Geth tracing options and returns the resulting traces and state.
     fn trace_geth(
         env: EnvWithHandlerCfg,
@@ -96,75 +105,112 @@ impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
         tx: &WithOtherFields<alloy_rpc_types::Transaction>,
         opts: GethDebugTracingOptions,
     ) -> TracingStateResult {
+        let transaction_info = TransactionInfo::from(&tx.inner).with_base_fee(block_base_fee(&env)?);
+        let (trace, state) = Self::geth_trace(env, db, transaction_info, opts)?;
+
+        Ok((TracingResult::Geth(vec![TraceResult::Success { result: trace, tx_hash: Some(tx.hash) }]), state))
+    }
+
+    /// Executes the environment with the tracer selected by the Geth tracing options
+    /// and returns the resulting trace and state.
+    fn geth_trace(
+        env: EnvWithHandlerCfg,
+        db: &EthCacheDatabase<P>,
+        transaction_info: TransactionInfo,
+        opts: GethDebugTracingOptions,
+    ) -> TracerResult<(GethTrace, EvmState)> {
         // Extract options
+        #[cfg(feature = "js-tracer")]
+        let timeout = opts.timeout.clone();
         let GethDebugTracingOptions { tracer_config, config, tracer, .. } = opts;
 
-        // Check if tracer is provided
-        if let Some(tracer) = tracer {
-            match tracer {
-                // Only support CallTracer for now
-                GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer) => {
-                    // Convert tracer config to call config
-                    let call_config = tracer_config
-                        .clone()
-                        .into_call_config()
-                        .map_err(|err| EthApiError::Transaction(TransactionError::Tracing(err.into())))?;
-
-                    // Initialize tracing inspector with call config
-                    let mut inspector =
-                        TracingInspector::new(TracingInspectorConfig::from_geth_call_config(&call_config));
-
-                    // Build EVM with environment and inspector
-                    let eth_evm_config = EthEvmConfig::new(Arc::new(Default::default()));
-
-                    let res = {
-                        let mut evm = eth_evm_config.evm_with_env_and_inspector(db.0.clone(), env, &mut inspector);
-
-                        // Execute transaction
-                        evm.transact().map_err(|err| TransactionError::Tracing(err.into()))?
-                    };
-
-                    // Get call traces
-                    let call_frame = inspector.into_geth_builder().geth_call_traces(
-                        tracer_config.into_call_config().map_err(|err| TransactionError::Tracing(err.into()))?,
-                        res.result.gas_used(),
-                    );
-
-                    // Return success trace result
-                    return Ok((
-                        TracingResult::Geth(vec![TraceResult::Success {
-                            result: call_frame.into(),
-                            tx_hash: Some(tx.hash),
-                        }]),
-                        res.state,
-                    ));
-                }
-                // Return error for unsupported tracers
-                _ => {
-                    return Err(EthApiError::Transaction(TransactionError::Tracing(
-                        eyre!("only call tracer is currently supported").into(),
-                    )))
-                }
+        // Use default tracer if no tracer is provided
+        let Some(tracer) = tracer else {
+            let mut inspector = TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+            let res = Self::inspect(env, db, &mut inspector)?;
+
+            let gas_used = res.result.gas_used();
+            let return_value = res.result.into_output().unwrap_or_default();
+            let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
+            return Ok((frame.into(), res.state));
+        };
+
+        let tracer = match tracer {
+            GethDebugTracerType::BuiltInTracer(tracer) => tracer,
+            #[cfg(feature = "js-tracer")]
+            GethDebugTracerType::JsTracer(code) => {
+                return Self::js_trace(env, db, transaction_info, code, tracer_config.into_json(), timeout.as_deref())
+            }
+            #[cfg(not(feature = "js-tracer"))]
+            GethDebugTracerType::JsTracer(_) => {
+                return Err(TransactionError::Tracing(eyre!("JS tracers require the `js-tracer` feature").into()).into())
+            }
+        };
+
+        match tracer {
+            GethDebugBuiltInTracerType::CallTracer => {
+                let call_config =
+                    tracer_config.into_call_config().map_err(|err| TransactionError::Tracing(err.into()))?;
+                let mut inspector = TracingInspector::new(TracingInspectorConfig::from_geth_call_config(&call_config));
+                let res = Self::inspect(env, db, &mut inspector)?;
+
+                let frame = inspector.into_geth_builder().geth_call_traces(call_config, res.result.gas_used());
+                Ok((frame.into(), res.state))
+            }
+            GethDebugBuiltInTracerType::FlatCallTracer => {
+                let flat_call_config =
+                    tracer_config.into_flat_call_config().map_err(|err| TransactionError::Tracing(err.into()))?;
+                let mut inspector =
+                    TracingInspector::new(TracingInspectorConfig::from_flat_call_config(&flat_call_config));
+                let res = Self::inspect(env, db, &mut inspector)?;
+
+                let frame = inspector.into_parity_builder().into_localized_transaction_traces(transaction_info);
+                Ok((GethTrace::FlatCallTracer(frame), res.state))
+            }
+            GethDebugBuiltInTracerType::PreStateTracer => {
+                let prestate_config =
+                    tracer_config.into_pre_state_config().map_err(|err| TransactionError::Tracing(err.into()))?;
+                let mut inspector =
+                    TracingInspector::new(TracingInspectorConfig::from_geth_prestate_config(&prestate_config));
+                let res = Self::inspect(env, db, &mut inspector)?;
+
+                // The prestate is read from the database, which doesn't contain the changes yet.
+                let frame = inspector.into_geth_builder().geth_prestate_traces(&res, &prestate_config, &db.0)?;
+                Ok((frame.into(), res.state))
+            }
+            GethDebugBuiltInTracerType::FourByteTracer => {
+                let mut inspector = FourByteInspector::default();
+                let res = Self::inspect(env, db, &mut inspector)?;
+
+                Ok((FourByteFrame::from(&inspector).into(), res.state))
+            }
+            GethDebugBuiltInTracerType::MuxTracer => {
+                let mux_config =
+                    tracer_config.into_mux_config().map_err(|err| TransactionError::Tracing(err.into()))?;
+                let mut inspector =
+                    MuxInspector::try_from_config(mux_config).map_err(|err| TransactionError::Tracing(err.into()))?;
+                let res = Self::inspect(env, db, &mut inspector)?;
+
+                let frame = inspector.try_into_mux_frame(&res, &db.0, transaction_info)?;
+                Ok((frame.into(), res.state))
+            }
+            GethDebugBuiltInTracerType::NoopTracer => {
+                // The transaction is still executed to return its state changes.
+                let res = Self::inspect(env, db, NoOpInspector)?;
+                Ok((NoopFrame::default().into(), res.state))
             }
         }
+    }
 
-        // Use default tracer
-        let mut inspector = TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+    /// Executes the environment on top of the database with the given inspector.
+    fn inspect<I>(env: EnvWithHandlerCfg, db: &EthCacheDatabase<P>, inspector: I) -> TracerResult<ResultAndState>
+    where
+        I: GetInspector<CacheDB<EthDatabase<P>>>,
+    {
         let eth_evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+        let mut evm = eth_evm_config.evm_with_env_and_inspector(db.0.clone(), env, inspector);
 
-        let res = {
-            let mut evm = eth_evm_config.evm_with_env_and_inspector(db.0.clone(), env, &mut inspector);
-            // Execute transaction
-            evm.transact().map_err(|err| TransactionError::Tracing(err.into()))?
-        };
-
-        let gas_used = res.result.gas_used();
-        let return_value = res.result.into_output().unwrap_or_default();
-        let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
-        Ok((
-            TracingResult::Geth(vec![TraceResult::Success { result: frame.into(), tx_hash: Some(tx.hash) }]),
-            res.state,
-        ))
+        Ok(evm.transact().map_err(|err| TransactionError::Tracing(err.into()))?)
     }
 
     /// Traces the transaction with Parity tracing options and returns the resulting traces and state.
@@ -175,12 +221,7 @@ impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
         tracing_config: TracingInspectorConfig,
     ) -> TracingStateResult {
         // Get block base fee
-        let block_base_fee = env
-            .env
-            .block
-            .basefee
-            .try_into()
-            .map_err(|err: FromUintError<u128>| TransactionError::Tracing(err.into()))?;
+        let block_base_fee = block_base_fee(&env)?;
 
         // Initialize tracing inspector with given config
         let mut inspector = TracingInspector::new(tracing_config);
@@ -206,32 +247,127 @@ impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
         ))
     }
 
+    /// Executes the environment and returns the requested Parity traces (trace, state diff and
+    /// VM trace) along with the resulting state.
+    fn trace_parity_results(
+        env: EnvWithHandlerCfg,
+        db: &EthCacheDatabase<P>,
+        trace_types: &HashSet<TraceType>,
+    ) -> TracerResult<(TraceResults, EvmState)> {
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::from_parity_config(trace_types));
+        let eth_evm_config = EthEvmConfig::new(Arc::new(Default::default()));
+
+        let res = {
+            let mut evm = eth_evm_config.evm_with_env_and_inspector(db.0.clone(), env, &mut inspector);
+
+            // Execute transaction
+            evm.transact().map_err(|err| TransactionError::Tracing(err.into()))?
+        };
+
+        // The state diff is computed against the database, which doesn't contain the changes yet.
+        let results = inspector.into_parity_builder().into_trace_results_with_state(&res, trace_types, &db.0)?;
+
+        Ok((results, res.state))
+    }
+
     /// Trace the block in the parity format.
     pub fn trace_block(self) -> TracerResult<Option<Vec<LocalizedTransactionTrace>>> {
         let txs = self.transactions.clone();
         Ok(Some(self.trace_transactions(TracingResult::as_parity, &txs)?))
     }
 
-    /// Returns the debug trace in the Geth.
-    /// Currently only supports the call tracer or the default tracer.
+    /// Returns the debug trace in the Geth format.
     pub fn debug_block(self) -> TracerResult<Vec<TraceResult>> {
         let txs = self.transactions.clone();
         self.trace_transactions(TracingResult::as_geth, &txs)
     }
 
     pub fn debug_transaction(mut self, transaction_hash: B256) -> TracerResult<GethTrace> {
+        // We only want to trace the transaction with the given hash.
+        let tx = self.replay_until(transaction_hash)?;
+        let trace = self
+            .trace_transactions(TracingResult::as_geth, &[tx])?
+            .first()
+            .cloned()
+            .ok_or(TransactionError::Tracing(eyre!("No trace found").into()))?;
+
+        match trace {
+            TraceResult::Success { result, .. } => Ok(result),
+            TraceResult::Error { error, .. } => Err(TransactionError::Tracing(error.into()).into()),
+        }
+    }
+
+    /// Returns the parity traces of the transaction with the given hash.
+    pub fn trace_transaction(mut self, transaction_hash: B256) -> TracerResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let tx = self.replay_until(transaction_hash)?;
+        Ok(Some(self.trace_transactions(TracingResult::as_parity, &[tx])?))
+    }
+
+    /// Replays the transaction with the given hash and returns the requested traces.
+    pub fn replay_transaction(
+        mut self,
+        transaction_hash: B256,
+        trace_types: &HashSet<TraceType>,
+    ) -> TracerResult<TraceResults> {
+        let tx = self.replay_until(transaction_hash)?;
+        if tx.other.get("reverted").is_some() {
+            return Ok(default_failure_trace_results());
+        }
+
+        let env = env_with_tx(&self.env, &tx)?;
+        Ok(Self::trace_parity_results(env, &self.db, trace_types)?.0)
+    }
+
+    /// Replays all the transactions of the block and returns the requested traces for each of them.
+    pub fn replay_block(self, trace_types: &HashSet<TraceType>) -> TracerResult<Vec<TraceResultsWithTransactionHash>> {
+        let mut results = Vec::with_capacity(self.transactions.len());
+        let mut transactions = self.transactions.iter().peekable();
+        let mut db = self.db;
+
+        while let Some(tx) = transactions.next() {
+            let (full_trace, state_changes) = if tx.other.get("reverted").is_some() {
+                (default_failure_trace_results(), HashMap::default())
+            } else {
+                Self::trace_parity_results(env_with_tx(&self.env, tx)?, &db, trace_types)?
+            };
+            results.push(TraceResultsWithTransactionHash { full_trace, transaction_hash: tx.hash });
+
+            // Only commit to the database if there are more transactions to process.
+            if transactions.peek().is_some() {
+                db.0.commit(state_changes);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Executes the transaction requests one after the other on top of the state of the block,
+    /// and returns the requested traces for each of them.
+    pub fn trace_calls(self, calls: Vec<(TransactionRequest, HashSet<TraceType>)>) -> TracerResult<Vec<TraceResults>> {
+        let mut results = Vec::with_capacity(calls.len());
+        let mut calls = calls.into_iter().peekable();
+        let mut db = self.db;
+
+        while let Some((request, trace_types)) = calls.next() {
+            let env = env_with_request(&self.env, &request);
+            let (trace_results, state_changes) = Self::trace_parity_results(env, &db, &trace_types)?;
+            results.push(trace_results);
+
+            // Each call is executed on top of the state changes of the previous ones.
+            if calls.peek().is_some() {
+                db.0.commit(state_changes);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Executes all the transactions of the block preceding the transaction with the
+    /// given hash and returns the transaction.
+    fn replay_until(&mut self, transaction_hash: B256) -> TracerResult<WithOtherFields<alloy_rpc_types::Transaction>> {
         for tx in self.transactions.clone() {
             if tx.hash == transaction_hash {
-                // We only want to trace the transaction with the given hash.
-                let trace = self
-                    .trace_transactions(TracingResult::as_geth, &[tx])?
-                    .first()
-                    .cloned()
-                    .ok_or(TransactionError::Tracing(eyre!("No trace found").into()))?;
-                return match trace {
-                    TraceResult::Success { result, .. } => Ok(result),
-                    TraceResult::Error { error, .. } => Err(TransactionError::Tracing(error.into()).into()),
-                };
+                return Ok(tx);
             }
 
             let env = env_with_tx(&self.env, &tx)?;
@@ -246,11 +382,11 @@ impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
 
     /// Debugs a transaction request by tracing it using the provided tracing options.
     ///
-    /// This function returns an error if the tracing options are not supported or if there is an issue
-    /// with the EVM environment or transaction execution.
-    pub fn debug_transaction_request(self, _request: &TransactionRequest) -> TracerResult<GethTrace> {
+    /// The request is executed on top of the state of the block, after applying the state
+    /// and block overrides of the tracing options.
+    pub fn debug_transaction_request(self, request: &TransactionRequest) -> TracerResult<GethTrace> {
         // Attempt to get Geth tracing options from the provided tracing options.
-        let opts = self
+        let GethDebugTracingCallOptions { tracing_options, state_overrides, block_overrides } = self
             .tracing_options
             .as_geth_call()
             .ok_or_else(|| {
@@ -261,67 +397,19 @@ impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
             })?
             .clone();
 
-        // Extract the tracing options from the obtained Geth tracing options.
-        let GethDebugTracingCallOptions { tracing_options, .. } = opts;
-        let GethDebugTracingOptions { tracer, tracer_config, .. } = tracing_options;
-
-        // Check if a tracer is provided.
-        if let Some(tracer) = tracer {
-            match tracer {
-                // Only support CallTracer for now.
-                GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer) => {
-                    // Build the EVM environment using the provided configuration and request.
-
-                    // TODO: build_call_evm_env not available anymore, to be discussed
-                    // let env = build_call_evm_env(
-                    //     CfgEnvWithHandlerCfg { cfg_env: self.env.cfg.clone(), handler_cfg: self.env.handler_cfg },
-                    //     self.env.block.clone(),
-                    //     request.clone(),
-                    // )?;
-
-                    // Convert the tracer configuration into call configuration.
-                    let call_config =
-                        tracer_config.into_call_config().map_err(|err| TransactionError::Tracing(err.into()))?;
-
-                    // Create a new tracing inspector with the call configuration.
-                    let mut inspector =
-                        TracingInspector::new(TracingInspectorConfig::from_geth_call_config(&call_config));
-
-                    // Build EVM with environment and inspector.
-                    let eth_evm_config = EthEvmConfig::new(Arc::new(Default::default()));
-                    // TODO: we should not use default here to be discussed
-                    let gas_used = {
-                        let mut evm = eth_evm_config.evm_with_env_and_inspector(
-                            self.db.0,
-                            EnvWithHandlerCfg::default(),
-                            &mut inspector,
-                        );
-
-                        // Execute the transaction.
-                        let res = evm.transact().map_err(|err| TransactionError::Tracing(err.into()))?;
-
-                        // Capture the gas used before `evm` goes out of scope.
-                        res.result.gas_used()
-                    };
-
-                    // Get the call traces from the inspector.
-                    let frame = inspector.into_geth_builder().geth_call_traces(call_config, gas_used);
-
-                    // Return the obtained call traces.
-                    return Ok(frame.into());
-                }
+        let mut db = self.db;
+        let mut env = self.env;
 
-                // Return an error for unsupported tracers.
-                _ => {
-                    return Err(EthApiError::Transaction(TransactionError::Tracing(
-                        eyre!("only call tracer is currently supported").into(),
-                    )))
-                }
-            }
+        // Apply the overrides before building the call environment, which depends on the block environment.
+        if let Some(block_overrides) = block_overrides {
+            apply_block_overrides(block_overrides, &mut db.0, &mut env.env.block);
         }
+        if let Some(state_overrides) = state_overrides {
+            apply_state_overrides(state_overrides, &mut db.0)?;
+        }
+        let env = env_with_request(&env, request);
 
-        // Return a default Geth trace if no tracer is provided.
-        Ok(GethTrace::Default(Default::default()))
+        Ok(Self::geth_trace(env, &db, TransactionInfo::default(), tracing_options)?.0)
     }
 
     /// Traces the provided transactions using the given closure.
@@ -367,6 +455,35 @@ impl<P: EthereumProvider + Send + Sync + Clone> Tracer<P> {
     }
 }
 
+/// Returns the environment with the transaction env built from the given transaction request.
+fn env_with_request(env: &EnvWithHandlerCfg, request: &TransactionRequest) -> EnvWithHandlerCfg {
+    let mut block = env.env.block.clone();
+
+    // Requests without fees are executed with a zero base fee, as done by Geth.
+    if request.gas_price.is_none() && request.max_fee_per_gas.is_none() {
+        block.basefee = U256::ZERO;
+    }
+    let tx_env = tx_env_from_request(request, block.gas_limit.saturating_to(), env.env.cfg.chain_id);
+
+    EnvWithHandlerCfg { env: Env::boxed(env.env.cfg.clone(), block, tx_env), handler_cfg: env.handler_cfg }
+}
+
+/// Returns the Parity trace results of a transaction which reverted on Starknet, matching
+/// the traces returned by [`TracingResult::default_failure`].
+fn default_failure_trace_results() -> TraceResults {
+    TraceResults {
+        output: Bytes::default(),
+        state_diff: None,
+        trace: TracingInspector::default().into_parity_builder().into_transaction_traces(),
+        vm_trace: None,
+    }
+}
+
+/// Returns the base fee of the block of the environment.
+fn block_base_fee(env: &EnvWithHandlerCfg) -> TracerResult<u128> {
+    Ok(env.env.block.basefee.try_into().map_err(|err: FromUintError<u128>| TransactionError::Tracing(err.into()))?)
+}
+
 /// Returns the environment with the transaction env updated to the given transaction.
 fn env_with_tx(
     env: &EnvWithHandlerCfg,
@@ -0,0 +1,64 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+use alloy_rpc_types_engine::{Claims, JwtSecret};
+use kakarot_rpc::test_utils::{
+    fixtures::{katana, setup},
+    katana::Katana,
+    rpc::{start_kakarot_admin_rpc_server, RawRpcParamsBuilder},
+};
+use reqwest::{header::AUTHORIZATION, StatusCode};
+use rstest::*;
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_admin_server_requires_jwt(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let dir = tempfile::tempdir().expect("Failed to create temporary directory");
+    let jwt_secret_path = dir.path().join("jwt.hex");
+    let (server_addr, server_handle) = start_kakarot_admin_rpc_server(&katana, jwt_secret_path.clone())
+        .await
+        .expect("Error setting up Kakarot admin RPC server");
+
+    // The secret is created by the server when the file is missing, only readable by its owner.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(&jwt_secret_path).expect("Failed to read JWT secret metadata");
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+    let secret = JwtSecret::from_file(&jwt_secret_path).expect("Failed to read JWT secret");
+    let iat = SystemTime::now().duration_since(UNIX_EPOCH).expect("Invalid system time").as_secs();
+    let jwt = secret.encode(&Claims { iat, exp: None }).expect("Failed to encode JWT");
+
+    let url = format!("http://localhost:{}", server_addr.port());
+    let body = RawRpcParamsBuilder::new("txpool_status").build();
+    let reqwest_client = reqwest::Client::new();
+
+    // When
+    let unauthenticated = reqwest_client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .body(body.clone())
+        .send()
+        .await
+        .expect("Failed to call admin RPC");
+    let authenticated = reqwest_client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header(AUTHORIZATION, format!("Bearer {jwt}"))
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to call admin RPC");
+
+    // Then
+    assert_eq!(unauthenticated.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(authenticated.status(), StatusCode::OK);
+    let response: Value = authenticated.json().await.expect("Failed to deserialize response body");
+    assert!(response["result"].is_object());
+
+    drop(server_handle);
+}
@@ -0,0 +1,72 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+
+use alloy_primitives::U64;
+use alloy_rpc_types::{Filter, FilterChanges};
+use kakarot_rpc::test_utils::{
+    fixtures::{katana, setup},
+    katana::Katana,
+    rpc::{send_request, start_kakarot_rpc_server, RawRpcParamsBuilder},
+};
+use rstest::*;
+use serde_json::Value;
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_block_filter(#[future] katana: Katana, _setup: ()) {
+    // Start the Kakarot RPC server
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+
+    // Install a block filter
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_newBlockFilter").build()).await;
+    let id: U64 = serde_json::from_value(response["result"].clone()).expect("Failed to deserialize filter id");
+
+    // No block was added since the filter was installed
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_getFilterChanges").add_param(id).build()).await;
+    let changes: FilterChanges = serde_json::from_value(response["result"].clone()).expect("Failed to deserialize");
+    assert_eq!(changes, FilterChanges::Empty);
+
+    // Uninstall the filter
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_uninstallFilter").add_param(id).build()).await;
+    assert_eq!(response["result"], Value::Bool(true));
+
+    // The filter can't be polled anymore
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_getFilterChanges").add_param(id).build()).await;
+    assert_eq!(response["error"]["message"], "filter not found");
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_log_filter(#[future] katana: Katana, _setup: ()) {
+    // Start the Kakarot RPC server
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+
+    // Install a log filter over all the blocks
+    let filter = Filter::new().from_block(0);
+    let response =
+        send_request(port, RawRpcParamsBuilder::new("eth_newFilter").add_param(filter.clone()).build()).await;
+    let id: U64 = serde_json::from_value(response["result"].clone()).expect("Failed to deserialize filter id");
+
+    // The filter returns the same logs as eth_getLogs
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_getFilterLogs").add_param(id).build()).await;
+    let filter_logs = response["result"].clone();
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_getLogs").add_param(filter).build()).await;
+    assert_eq!(filter_logs, response["result"]);
+
+    // Block filters can't return logs
+    let response = send_request(port, RawRpcParamsBuilder::new("eth_newBlockFilter").build()).await;
+    let block_filter_id: U64 = serde_json::from_value(response["result"].clone()).expect("Failed to deserialize");
+    let response =
+        send_request(port, RawRpcParamsBuilder::new("eth_getFilterLogs").add_param(block_filter_id).build()).await;
+    assert_eq!(response["error"]["message"], "filter not found");
+
+    drop(server_handle);
+}
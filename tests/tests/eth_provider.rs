@@ -3,30 +3,39 @@
 use crate::tests::mempool::create_sample_transactions;
 use alloy_consensus::{TxEip1559, TxLegacy};
 use alloy_eips::{eip2718::Encodable2718, BlockNumberOrTag};
-use alloy_primitives::{address, bytes, Address, Bytes, Signature, TxKind, B256, U256, U64};
+use alloy_primitives::{address, bytes, keccak256, Address, Bytes, Signature, TxKind, B256, U256, U64};
 use alloy_rpc_types::{
     request::TransactionInput,
     serde_helpers::JsonStorageKey,
+    simulate::{SimBlock, SimulatePayload},
     state::{AccountOverride, StateOverride},
-    Filter, FilterBlockOption, FilterChanges, Log, RpcBlockHash, Topic, TransactionRequest,
+    AccessListItem, BlockOverrides, Bundle, Filter, FilterBlockOption, FilterChanges, Log, RpcBlockHash, StateContext,
+    Topic, TransactionIndex, TransactionRequest,
 };
 use alloy_sol_types::{sol, SolCall};
 use arbitrary::Arbitrary;
 use kakarot_rpc::{
     client::{KakarotTransactions, TransactionHashProvider},
+    config::Config,
     into_via_try_wrapper,
-    models::felt::Felt252Wrapper,
-    providers::eth_provider::{
-        constant::{MAX_LOGS, STARKNET_MODULUS},
-        database::{
-            ethereum::EthereumTransactionStore,
-            filter,
-            filter::EthDatabaseFilterBuilder,
-            types::transaction::{EthStarknetHashes, StoredEthStarknetTransactionHash, StoredTransaction},
+    models::{bundle::CallBundle, felt::Felt252Wrapper},
+    providers::{
+        eth_provider::{
+            bundle::MAX_BUNDLE_TRANSACTIONS,
+            constant::{CALL_REQUEST_GAS_LIMIT, MAX_LOGS, STARKNET_MODULUS},
+            database::{
+                ethereum::EthereumTransactionStore,
+                filter,
+                filter::EthDatabaseFilterBuilder,
+                types::transaction::{EthStarknetHashes, StoredEthStarknetTransactionHash, StoredTransaction},
+            },
+            error::{EthApiError, TransactionError},
+            provider::{EthDataProvider, EthereumProvider},
+            starknet::{kakarot_core::starknet_address, relayer::Relayer},
+            BlockProvider, ChainProvider, GasProvider, LogProvider, ReceiptProvider, StateProvider,
+            TransactionProvider,
         },
-        provider::EthereumProvider,
-        starknet::relayer::Relayer,
-        BlockProvider, ChainProvider, GasProvider, LogProvider, ReceiptProvider, StateProvider, TransactionProvider,
+        sn_provider::{storage_proof::StorageProof, StarknetProvider},
     },
     test_utils::{
         eoa::Eoa,
@@ -45,6 +54,7 @@ use starknet::{
     core::types::{BlockId, BlockTag, Felt},
 };
 use std::sync::Arc;
+use url::Url;
 
 #[rstest]
 #[awt]
@@ -327,16 +337,37 @@ async fn test_get_logs_limit(#[future] katana: Katana, _setup: ()) {
     // Get the Ethereum provider from Katana.
     let provider = katana.eth_provider();
 
-    // Set the limit of logs to be retrieved.
+    // Set the limit of logs to be retrieved, the configuration is already read by the fixture.
     std::env::set_var("MAX_LOGS", "500");
+    Config::reload_from_env().expect("Failed to reload the configuration");
 
     // Add mock logs to the Katana instance's database.
     // The number of logs added is MAX_LOGS + 20, ensuring there are more logs than the limit.
     katana.add_mock_logs(((*MAX_LOGS).unwrap() + 20) as usize).await;
 
-    // Assert that the number of logs returned by filter_logs is equal to the limit.
+    // Assert that the query fails instead of returning a truncated list of logs.
     // This ensures that the log retrieval respects the MAX_LOGS constraint.
-    assert_eq!(filter_logs(Filter::default(), provider.clone()).await.len(), (*MAX_LOGS).unwrap() as usize);
+    let err = provider.get_logs(Filter::default()).await.unwrap_err();
+    assert!(matches!(err, EthApiError::LogsLimitExceeded(limit, Some(_)) if limit == (*MAX_LOGS).unwrap()));
+    assert!(err.to_string().starts_with(&format!("query returned more than {} results", (*MAX_LOGS).unwrap())));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_ordering(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let provider = katana.eth_provider();
+
+    // When
+    let logs = filter_logs(Filter::default(), provider.clone()).await;
+
+    // Then
+    let positions: Vec<_> = logs.iter().map(|log| (log.block_number, log.log_index)).collect();
+    let mut sorted_positions = positions.clone();
+    sorted_positions.sort_unstable();
+    assert!(!logs.is_empty());
+    assert_eq!(positions, sorted_positions);
 }
 
 #[rstest]
@@ -466,6 +497,33 @@ async fn test_get_logs_address(#[future] katana: Katana, _setup: ()) {
     assert_eq!(filter_logs(filter, provider.clone()).await.len(), 2);
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_logs_bloom_mismatch(#[future] katana: Katana, _setup: ()) {
+    // Given
+    // A log which address isn't in the logs bloom of its block.
+    let provider = katana.eth_provider();
+    let stored_log = katana.all_logs()[0].clone();
+    let address = Address::random();
+    let log = Log {
+        inner: alloy_primitives::Log { address, data: stored_log.inner.data.clone() },
+        log_index: Some(u64::MAX),
+        ..stored_log
+    };
+    katana.add_log_to_database(log.clone()).await;
+    let block_hash = log.block_hash.expect("Failed to get the block hash of the log");
+
+    // When
+    let logs_by_range = filter_logs(Filter::new().address(address), provider.clone()).await;
+    let logs_by_hash = filter_logs(Filter::new().address(address).at_block_hash(block_hash), provider.clone()).await;
+
+    // Then
+    // The block is skipped when the blocks are selected from their bloom, but not when it is queried by hash.
+    assert!(logs_by_range.is_empty());
+    assert_eq!(logs_by_hash, vec![log]);
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -536,12 +594,74 @@ async fn test_estimate_gas(#[future] counter: (Katana, KakarotEvmContract), _set
     };
 
     // When
-    let estimate = eth_provider.estimate_gas(request, None).await.unwrap();
+    let estimate = eth_provider.estimate_gas(request, None, None).await.unwrap();
 
     // Then
     assert!(estimate > U256::from(0));
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_gas_with_state_override(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let contract_address = Address::random();
+    // NUMBER PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+    let code = bytes!("4360005260206000f3");
+
+    let mut state_override = StateOverride::default();
+    state_override.insert(contract_address, AccountOverride { code: Some(code), ..Default::default() });
+
+    let request = TransactionRequest {
+        from: Some(katana.eoa().evm_address().expect("Failed to get eoa address")),
+        to: Some(TxKind::Call(contract_address)),
+        ..Default::default()
+    };
+
+    // When
+    let estimate = eth_provider
+        .estimate_gas(request.clone(), None, Some(state_override.clone()))
+        .await
+        .expect("Failed to estimate gas with state override");
+    let gas_limit = estimate.to::<u64>();
+    let below_estimate = TransactionRequest { gas: Some(gas_limit - 1), ..request.clone() };
+    let at_estimate = TransactionRequest { gas: Some(gas_limit), ..request };
+
+    // Then
+    // Intrinsic gas and the 17 gas of the code.
+    assert_eq!(estimate, U256::from(21017));
+    assert!(eth_provider.call(below_estimate, None, Some(state_override.clone()), None).await.is_err());
+    assert!(eth_provider.call(at_estimate, None, Some(state_override), None).await.is_ok());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_estimate_gas_exceeds_allowance(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let from = Address::random();
+
+    // The sender can only pay for 1000 gas.
+    let mut state_override = StateOverride::default();
+    state_override.insert(from, AccountOverride { balance: Some(U256::from(1000)), ..Default::default() });
+
+    let request = TransactionRequest {
+        from: Some(from),
+        to: Some(TxKind::Call(Address::random())),
+        gas_price: Some(1),
+        ..Default::default()
+    };
+
+    // When
+    let err = eth_provider.estimate_gas(request, None, Some(state_override)).await.unwrap_err();
+
+    // Then
+    assert!(matches!(err, EthApiError::Transaction(TransactionError::GasRequiredExceedsAllowance(1000))));
+    assert_eq!(err.to_string(), "gas required exceeds allowance (1000)");
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -552,8 +672,8 @@ async fn test_fee_history(#[future] katana: Katana, _setup: ()) {
     // Retrieve the most recent block number.
     let newest_block = katana.block_number();
 
-    // To ensure that the range includes all mocked blocks.
-    let block_count = u64::MAX;
+    // To ensure that the range includes all mocked blocks, from the genesis block.
+    let block_count = newest_block + 1;
 
     // Get the total number of blocks in the database.
     let nbr_blocks = katana.headers.len();
@@ -571,6 +691,40 @@ async fn test_fee_history(#[future] katana: Katana, _setup: ()) {
 
     // Verify that the oldest block in the fee history is equal to zero.
     assert_eq!(fee_history.oldest_block, 0);
+
+    // Verify that the blob fields are zeroed, as Kakarot doesn't support blob transactions.
+    assert_eq!(fee_history.base_fee_per_blob_gas, vec![0; nbr_blocks + 1]);
+    assert_eq!(fee_history.blob_gas_used_ratio, vec![0.; nbr_blocks]);
+
+    // Verify that the rewards are not returned without percentiles.
+    assert!(fee_history.reward.is_none());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fee_history_reward_percentiles(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let newest_block = katana.block_number();
+    let block_count = U64::from(3);
+    let percentiles = vec![10., 50., 90.];
+
+    // When
+    let fee_history =
+        eth_provider.fee_history(block_count, newest_block.into(), Some(percentiles.clone())).await.unwrap();
+    let invalid_percentiles =
+        eth_provider.fee_history(block_count, newest_block.into(), Some(vec![50., 10.])).await.unwrap_err();
+    let too_many_percentiles =
+        eth_provider.fee_history(block_count, newest_block.into(), Some(vec![50.; 101])).await.unwrap_err();
+
+    // Then
+    let reward = fee_history.reward.expect("Missing rewards");
+    assert_eq!(reward.len(), fee_history.gas_used_ratio.len());
+    assert!(reward.iter().all(|block_rewards| block_rewards.len() == percentiles.len()));
+    assert!(reward.iter().all(|block_rewards| block_rewards.windows(2).all(|w| w[0] <= w[1])));
+    assert!(matches!(invalid_percentiles, EthApiError::RethEthApi(_)));
+    assert!(matches!(too_many_percentiles, EthApiError::RethEthApi(_)));
 }
 
 #[rstest]
@@ -997,6 +1151,7 @@ async fn test_send_raw_transaction_pre_eip_155(#[future] katana_empty: Katana, _
     let hash = transaction_signed.hash();
     let random_hash = B256::random();
     std::env::set_var("WHITE_LISTED_EIP_155_TRANSACTION_HASHES", format!("{hash}, {random_hash}"));
+    Config::reload_from_env().expect("Failed to reload the configuration");
 
     let mempool_size = eth_client.mempool().pool_size();
     // Assert that the number of pending and total transactions in the mempool is 0
@@ -1231,7 +1386,6 @@ async fn test_call_with_state_override_balance_success(#[future] katana: Katana,
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
-#[ignore = "fails because of reth tracing utils temporarily removed in PR 1389"]
 async fn test_call_with_state_override_balance_failure(#[future] katana: Katana, _setup: ()) {
     // Obtain an Ethereum provider instance from the Katana instance
     let eth_provider = katana.eth_provider();
@@ -1265,6 +1419,36 @@ async fn test_call_with_state_override_balance_failure(#[future] katana: Katana,
     assert_eq!(err, "tracing error: transaction validation error: lack of funds (1000000000) for max fee (1000210001)");
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_with_block_override(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let contract_address = Address::random();
+    // NUMBER PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+    let code = bytes!("4360005260206000f3");
+
+    let mut state_override = StateOverride::default();
+    state_override.insert(contract_address, AccountOverride { code: Some(code), ..Default::default() });
+    let block_override = BlockOverrides { number: Some(U256::from(1234)), ..Default::default() };
+
+    let request = TransactionRequest {
+        from: Some(katana.eoa().evm_address().expect("Failed to get eoa address")),
+        to: Some(TxKind::Call(contract_address)),
+        ..Default::default()
+    };
+
+    // When
+    let output = eth_provider
+        .call(request, None, Some(state_override), Some(Box::new(block_override)))
+        .await
+        .expect("Failed to call with block override");
+
+    // Then
+    assert_eq!(U256::from_be_slice(&output), U256::from(1234));
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -1328,6 +1512,272 @@ async fn test_call_with_state_override_bytecode(#[future] plain_opcodes: (Katana
         .expect("Failed to set number in Counter contract");
 }
 
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_create_access_list(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = counter.0;
+    let counter = counter.1;
+    let eth_provider = katana.eth_provider();
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+    let counter_address = counter_address.try_into().expect("Failed to convert EVM address");
+
+    let selector = alloy_primitives::keccak256("inc()")[..4].to_vec();
+    let request = TransactionRequest {
+        from: Some(eoa_address),
+        to: Some(TxKind::Call(counter_address)),
+        input: TransactionInput { input: Some(selector.into()), data: None },
+        ..Default::default()
+    };
+
+    // When
+    let result = eth_provider.create_access_list(request, None).await.expect("Failed to create access list");
+
+    // Then
+    // The counter reads and writes its count in the slot 0 of its storage.
+    assert_eq!(result.error, None);
+    assert_eq!(result.access_list.0, vec![AccessListItem { address: counter_address, storage_keys: vec![B256::ZERO] }]);
+    assert!(result.gas_used > U256::from(21_000));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_proof(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = counter.0;
+    let counter = counter.1;
+    let eoa = katana.eoa();
+    eoa.call_evm_contract(&counter, "inc", &[], 0).await.expect("Failed to increment counter");
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+    let counter_address = counter_address.try_into().expect("Failed to convert EVM address");
+
+    // Katana doesn't serve `starknet_getStorageProof`, the proof is served by a mock node.
+    let starknet_proof = serde_json::json!({
+        "classes_proof": [],
+        "contracts_proof": {
+            "nodes": [{ "node_hash": "0x1", "node": { "left": "0x2", "right": "0x3" } }],
+            "contract_leaves_data": [{ "nonce": "0x0", "class_hash": "0x4", "storage_root": "0x5" }]
+        },
+        "contracts_storage_proofs": [[{ "node_hash": "0x5", "node": { "path": "0x6", "length": 2, "child": "0x7" } }]],
+        "global_roots": { "contracts_tree_root": "0x1", "classes_tree_root": "0x8", "block_hash": "0x9" }
+    });
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "method": "starknet_getStorageProof",
+            "params": { "contract_addresses": [format!("{:#x}", starknet_address(counter_address))] },
+        })))
+        .with_header("content-type", "application/json")
+        .with_body(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": starknet_proof }).to_string())
+        .create_async()
+        .await;
+
+    let eth_provider = EthDataProvider::new(
+        katana.eth_provider().database().clone(),
+        StarknetProvider::new(katana.starknet_provider()),
+    )
+    .with_storage_proof_url(Url::parse(&server.url()).expect("Failed to parse mock server url"));
+
+    // When
+    let proof = eth_provider.get_proof(counter_address, vec![B256::ZERO], None).await.expect("Failed to get proof");
+
+    // Then
+    mock.assert_async().await;
+    assert_eq!(proof.address, counter_address);
+    assert_eq!(proof.nonce, eth_provider.transaction_count(counter_address, None).await.unwrap().to::<u64>());
+    assert_eq!(proof.balance, eth_provider.balance(counter_address, None).await.unwrap());
+    assert_eq!(proof.code_hash, keccak256(eth_provider.get_code(counter_address, None).await.unwrap()));
+    assert_eq!(proof.storage_hash, B256::with_last_byte(5));
+
+    // The Ethereum proofs are empty, the Starknet proof is only returned in `starknetStorageProof`.
+    assert!(proof.account_proof.is_empty());
+    assert_eq!(proof.storage_proof.len(), 1);
+    assert_eq!(proof.storage_proof[0].key, JsonStorageKey(B256::ZERO));
+    assert_eq!(proof.storage_proof[0].value, U256::from(1));
+    assert!(proof.storage_proof[0].proof.is_empty());
+    assert_eq!(
+        serde_json::from_value::<StorageProof>(proof.other["starknetStorageProof"].clone()).unwrap(),
+        serde_json::from_value::<StorageProof>(starknet_proof).unwrap()
+    );
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_simulate_v1(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = counter.0;
+    let eth_provider = katana.eth_provider();
+    let (eoa_address, call) = counter_calls(&katana, &counter.1);
+    let recipient = Address::random();
+
+    let transfer = TransactionRequest {
+        from: Some(eoa_address),
+        to: Some(TxKind::Call(recipient)),
+        value: Some(U256::from(1)),
+        ..Default::default()
+    };
+    let payload = SimulatePayload {
+        block_state_calls: vec![
+            SimBlock { calls: vec![call("inc()"), call("inc()")], ..Default::default() },
+            SimBlock { calls: vec![call("count()"), transfer], ..Default::default() },
+        ],
+        trace_transfers: true,
+        ..Default::default()
+    };
+
+    // When
+    let blocks = eth_provider.simulate_v1(payload, None).await.expect("Failed to simulate calls");
+
+    // Then
+    // The second block is executed on top of the state changes of the first one.
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[1].inner.header.number, blocks[0].inner.header.number + 1);
+    assert_eq!(blocks[1].inner.header.parent_hash, blocks[0].inner.header.hash);
+    assert!(blocks.iter().flat_map(|block| &block.calls).all(|call| call.status));
+    assert_eq!(U256::from_be_slice(&blocks[1].calls[0].return_data), U256::from(2));
+    assert_eq!(blocks[0].inner.header.gas_used, blocks[0].calls.iter().map(|call| call.gas_used).sum::<u64>());
+
+    // The native transfer is traced with an ERC-7528 transfer log.
+    let transfer_logs = &blocks[1].calls[1].logs;
+    assert_eq!(transfer_logs.len(), 1);
+    assert_eq!(transfer_logs[0].inner.address, address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeEEeE"));
+    assert_eq!(transfer_logs[0].block_hash, Some(blocks[1].inner.header.hash));
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_simulate_v1_caps_explicit_gas(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let looping_address = Address::random();
+    // JUMPDEST PUSH1 0x00 JUMP: loops until all the gas of the call is consumed.
+    let mut state_overrides = StateOverride::default();
+    state_overrides.insert(looping_address, AccountOverride { code: Some(bytes!("5b600056")), ..Default::default() });
+    let call = TransactionRequest {
+        from: Some(katana.eoa().evm_address().expect("Failed to get eoa address")),
+        to: Some(TxKind::Call(looping_address)),
+        gas: Some(CALL_REQUEST_GAS_LIMIT * 2),
+        ..Default::default()
+    };
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimBlock {
+            block_overrides: Some(BlockOverrides { gas_limit: Some(u64::MAX), ..Default::default() }),
+            state_overrides: Some(state_overrides),
+            calls: vec![call],
+        }],
+        ..Default::default()
+    };
+
+    // When
+    let blocks = eth_provider.simulate_v1(payload, None).await.expect("Failed to simulate calls");
+
+    // Then
+    // The explicit gas of the call is capped by the gas limit of the request.
+    assert!(!blocks[0].calls[0].status);
+    assert!(blocks[0].calls[0].gas_used <= CALL_REQUEST_GAS_LIMIT);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_many(#[future] counter: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = counter.0;
+    let eth_provider = katana.eth_provider();
+    let (_, call) = counter_calls(&katana, &counter.1);
+    let bundle = Bundle { transactions: vec![call("inc()"), call("inc()"), call("count()")], block_override: None };
+    let too_large_bundle =
+        Bundle { transactions: vec![call("count()"); MAX_BUNDLE_TRANSACTIONS + 1], block_override: None };
+    let genesis_context = StateContext {
+        block_number: Some(alloy_eips::BlockId::number(0)),
+        transaction_index: Some(TransactionIndex::Index(0)),
+    };
+
+    // When
+    let responses = eth_provider.call_many(bundle.clone(), None, None).await.expect("Failed to call bundle");
+    let too_large_err = eth_provider.call_many(too_large_bundle, None, None).await.unwrap_err();
+    let genesis_err = eth_provider.call_many(bundle, Some(genesis_context), None).await.unwrap_err();
+
+    // Then
+    // Each call is executed on top of the state changes of the previous ones.
+    assert_eq!(responses.len(), 3);
+    assert!(responses.iter().all(|response| response.error.is_none()));
+    assert_eq!(U256::from_be_slice(responses[2].value.as_ref().unwrap()), U256::from(2));
+    // The bundles are limited, and can't be executed in the middle of the genesis block which has no parent.
+    assert!(too_large_err.to_string().contains("too many transactions"));
+    assert!(genesis_err.to_string().contains("genesis block"));
+}
+
+/// Returns the address of the EOA of Katana, and a builder of its calls to the given function of
+/// the counter contract.
+fn counter_calls(katana: &Katana, counter: &KakarotEvmContract) -> (Address, impl Fn(&str) -> TransactionRequest) {
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+    let counter_address: Felt252Wrapper = counter.evm_address.into();
+    let counter_address: Address = counter_address.try_into().expect("Failed to convert EVM address");
+
+    let call = move |selector: &str| TransactionRequest {
+        from: Some(eoa_address),
+        to: Some(TxKind::Call(counter_address)),
+        input: TransactionInput { input: Some(alloy_primitives::keccak256(selector)[..4].to_vec().into()), data: None },
+        ..Default::default()
+    };
+    (eoa_address, call)
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_bundle(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_provider = katana.eth_provider();
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+    let chain_id = eth_provider.chain_id().await.unwrap_or_default().unwrap_or_default().to();
+    let nonce: u64 = eth_provider.transaction_count(eoa_address, None).await.unwrap().to();
+    let block_number = eth_provider.block_number().await.unwrap().to::<u64>();
+
+    let transfer = |nonce: u64| {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit: 21000,
+            to: TxKind::Call(Address::random()),
+            value: U256::from(1000),
+            input: Bytes::default(),
+            max_fee_per_gas: 875_000_000,
+            max_priority_fee_per_gas: 0,
+            access_list: Default::default(),
+        });
+        let signature = sign_message(katana.eoa().private_key(), transaction.signature_hash()).unwrap();
+        Bytes::from(TransactionSigned::from_transaction_and_signature(transaction, signature).encoded_2718())
+    };
+    let bundle = CallBundle {
+        txs: vec![transfer(nonce), transfer(nonce + 1)],
+        block_number: U64::from(block_number + 1),
+        state_block_number: BlockNumberOrTag::Number(block_number),
+        coinbase: None,
+        timestamp: None,
+        gas_limit: None,
+        base_fee: None,
+    };
+
+    // When
+    let response = eth_provider.call_bundle(bundle).await.expect("Failed to call bundle");
+
+    // Then
+    // The second transfer is executed on top of the nonce increment of the first one.
+    assert_eq!(response.results.len(), 2);
+    assert!(response.results.iter().all(|result| result.from_address == eoa_address && result.revert.is_none()));
+    assert!(response.results.iter().all(|result| result.gas_used == U64::from(21000)));
+    assert_eq!(response.total_gas_used, U64::from(42000));
+    assert_eq!(response.state_block_number, U64::from(block_number));
+}
+
 #[rstest]
 #[awt]
 #[tokio::test(flavor = "multi_thread")]
@@ -0,0 +1,96 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+
+use alloy_primitives::{Address, Bloom, Bytes, LogData, B256};
+use alloy_rpc_types::{Filter, Header, Log};
+use jsonrpsee::{
+    core::client::{Subscription, SubscriptionClientT},
+    rpc_params,
+    ws_client::WsClientBuilder,
+};
+use kakarot_rpc::{
+    providers::eth_provider::BlockProvider,
+    test_utils::{
+        fixtures::{katana, setup},
+        katana::Katana,
+        rpc::start_kakarot_rpc_server,
+    },
+};
+use rstest::*;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// Maximum time to wait for a notification from the server.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Receives the next notification of the subscription.
+async fn next<T: DeserializeOwned>(subscription: &mut Subscription<T>) -> T {
+    tokio::time::timeout(NOTIFICATION_TIMEOUT, subscription.next())
+        .await
+        .expect("Timed out waiting for a notification")
+        .expect("Subscription closed")
+        .expect("Failed to deserialize notification")
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_subscribe_new_heads_and_logs(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let client =
+        WsClientBuilder::default().build(format!("ws://{server_addr}")).await.expect("Failed to connect to the server");
+
+    let address = Address::with_last_byte(0xaa);
+    let mut new_heads: Subscription<Header> = client
+        .subscribe("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+        .await
+        .expect("newHeads subscription failed");
+    let mut logs: Subscription<Log> = client
+        .subscribe("eth_subscribe", rpc_params!["logs", Filter::new().address(address)], "eth_unsubscribe")
+        .await
+        .expect("logs subscription failed");
+
+    // When
+    let latest_block: u64 = katana.eth_provider().block_number().await.expect("Failed to get the block number").to();
+    let block_number = latest_block + 1;
+    let block_hash = B256::with_last_byte(0xbb);
+
+    let log = Log {
+        inner: alloy_primitives::Log {
+            address,
+            data: LogData::new_unchecked(vec![B256::with_last_byte(0xcc)], Bytes::from_static(&[0xcc])),
+        },
+        block_hash: Some(block_hash),
+        block_number: Some(block_number),
+        transaction_hash: Some(B256::with_last_byte(0xdd)),
+        transaction_index: Some(0),
+        log_index: Some(0),
+        ..Default::default()
+    };
+    let mut logs_bloom = Bloom::default();
+    logs_bloom.accrue_log(&log.inner);
+
+    katana.add_log_to_database(log.clone()).await;
+    katana
+        .add_transactions_with_header_to_database(
+            vec![],
+            Header {
+                number: block_number,
+                hash: block_hash,
+                logs_bloom,
+                base_fee_per_gas: Some(0),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    // Then
+    let header = next(&mut new_heads).await;
+    assert_eq!(header.number, block_number);
+    assert_eq!(header.hash, block_hash);
+    assert_eq!(next(&mut logs).await, log);
+
+    drop(server_handle);
+}
@@ -1,13 +1,25 @@
 #![allow(clippy::used_underscore_binding)]
 #![cfg(feature = "testing")]
 
-use alloy_primitives::B256;
+use alloy_primitives::{Address, B256, U256};
 use kakarot_rpc::{
-    providers::eth_provider::constant::Constant,
+    config::Config,
+    models::{
+        felt::Felt252Wrapper,
+        kakarot::{AccountClassHashes, AccountStatus},
+    },
+    providers::eth_provider::{
+        constant::Constant,
+        database::{ethereum::EthereumTransactionStore, types::transaction::EthStarknetHashes},
+        starknet::kakarot_core::{
+            core::KakarotCoreReader, starknet_address, KAKAROT_ADDRESS, UNINITIALIZED_ACCOUNT_CLASS_HASH,
+        },
+    },
     test_utils::{
+        eoa::Eoa,
         fixtures::{katana, setup},
         katana::Katana,
-        rpc::{start_kakarot_rpc_server, RawRpcParamsBuilder},
+        rpc::{send_request, start_kakarot_rpc_server, RawRpcParamsBuilder},
     },
 };
 use rstest::*;
@@ -76,6 +88,8 @@ async fn test_kakarot_get_config(#[future] katana: Katana, _setup: ()) {
     std::env::set_var("MAX_LOGS", max_logs.to_string());
     std::env::set_var("MAX_FELTS_IN_CALLDATA", max_felts_in_calldata.to_string());
     std::env::set_var("KAKAROT_ADDRESS", "0x03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb");
+    // The configuration is already read by the fixture.
+    Config::reload_from_env().expect("Failed to reload the configuration");
 
     // Hardcoded expected values
     let expected_constant = Constant {
@@ -91,22 +105,109 @@ async fn test_kakarot_get_config(#[future] katana: Katana, _setup: ()) {
         start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
 
     // Send the RPC request to get the configuration
-    let reqwest_client = reqwest::Client::new();
-    let res = reqwest_client
-        .post(format!("http://localhost:{}", server_addr.port()))
-        .header("Content-Type", "application/json")
-        .body(RawRpcParamsBuilder::new("kakarot_getConfig").build())
-        .send()
-        .await
-        .expect("kakarot_getConfig error");
+    let result = call_result(server_addr.port(), RawRpcParamsBuilder::new("kakarot_getConfig").build()).await;
 
     // Deserialize the response
-    let result_constant: Constant = serde_json::from_str(&res.text().await.expect("Failed to get response body"))
-        .and_then(|raw: Value| serde_json::from_value(raw["result"].clone()))
-        .expect("Failed to deserialize response body or convert result to Constant");
+    let result_constant: Constant = serde_json::from_value(result).expect("Failed to convert result to Constant");
 
     // Assert that the returned configuration matches the expected value
     assert_eq!(result_constant, expected_constant);
 
     drop(server_handle);
 }
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_kakarot_transaction_hashes(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eth_hash = B256::random();
+    let starknet_hash = Felt::from_hex("0x03d937c035c878245caf64531a5756109c53068da139362728feb561405371cb").unwrap();
+    katana
+        .eth_provider()
+        .database()
+        .upsert_transaction_hashes(EthStarknetHashes { eth_hash, starknet_hash })
+        .await
+        .expect("Failed to upsert transaction hash mapping");
+
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let call = |body: String| call_result(server_addr.port(), body);
+
+    // When
+    let found_eth_hash =
+        call(RawRpcParamsBuilder::new("kakarot_getEthTransactionHash").add_param(starknet_hash).build()).await;
+    let found_starknet_hash =
+        call(RawRpcParamsBuilder::new("kakarot_getStarknetTransactionHash").add_param(eth_hash).build()).await;
+    let unknown_hash =
+        call(RawRpcParamsBuilder::new("kakarot_getStarknetTransactionHash").add_param(B256::random()).build()).await;
+
+    // Then
+    assert_eq!(serde_json::from_value::<B256>(found_eth_hash).unwrap(), eth_hash);
+    assert_eq!(serde_json::from_value::<Felt>(found_starknet_hash).unwrap(), starknet_hash);
+    assert_eq!(unknown_hash, Value::Null);
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_kakarot_starknet_introspection(#[future] katana: Katana, _setup: ()) {
+    // Given
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get EOA address");
+    let undeployed_address = Address::random();
+
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let call = |body: String| call_result(server_addr.port(), body);
+
+    // When
+    let eoa_starknet_address =
+        call(RawRpcParamsBuilder::new("kakarot_getStarknetAddress").add_param(eoa_address).build()).await;
+    let eoa_status = call(RawRpcParamsBuilder::new("kakarot_getAccountStatus").add_param(eoa_address).build()).await;
+    let undeployed_status =
+        call(RawRpcParamsBuilder::new("kakarot_getAccountStatus").add_param(undeployed_address).build()).await;
+    let base_fee = call(RawRpcParamsBuilder::new("kakarot_getBaseFee").build()).await;
+    let gas_price = call(RawRpcParamsBuilder::new("eth_gasPrice").build()).await;
+    let class_hashes = call(RawRpcParamsBuilder::new("kakarot_getAccountClassHashes").build()).await;
+    let coinbase = call(RawRpcParamsBuilder::new("kakarot_getCoinbase").build()).await;
+
+    // Then
+    let eoa_starknet_address = serde_json::from_value::<Felt>(eoa_starknet_address).unwrap();
+    assert_eq!(eoa_starknet_address, starknet_address(eoa_address));
+
+    let eoa_status = serde_json::from_value::<AccountStatus>(eoa_status).unwrap();
+    assert_eq!(eoa_status.starknet_address, eoa_starknet_address);
+    assert!(eoa_status.deployed);
+    assert!(eoa_status.initialized);
+    assert!(eoa_status.class_hash.is_some());
+
+    let undeployed_status = serde_json::from_value::<AccountStatus>(undeployed_status).unwrap();
+    assert_eq!(undeployed_status.starknet_address, starknet_address(undeployed_address));
+    assert!(!undeployed_status.deployed);
+    assert!(!undeployed_status.initialized);
+    assert_eq!(undeployed_status.class_hash, None);
+
+    assert_eq!(serde_json::from_value::<U256>(base_fee).unwrap(), serde_json::from_value::<U256>(gas_price).unwrap());
+
+    let class_hashes = serde_json::from_value::<AccountClassHashes>(class_hashes).unwrap();
+    assert_eq!(class_hashes.uninitialized_account_class_hash, *UNINITIALIZED_ACCOUNT_CLASS_HASH);
+
+    let kakarot_coinbase = KakarotCoreReader::new(*KAKAROT_ADDRESS, katana.starknet_provider())
+        .get_coinbase()
+        .call()
+        .await
+        .expect("Failed to get the coinbase")
+        .coinbase;
+    let kakarot_coinbase: Address =
+        Felt252Wrapper::from(kakarot_coinbase).try_into().expect("Failed to convert the coinbase");
+    assert_eq!(serde_json::from_value::<Address>(coinbase).unwrap(), kakarot_coinbase);
+
+    drop(server_handle);
+}
+
+/// Sends the request to the Kakarot RPC server listening on the port and returns the result of the call.
+async fn call_result(port: u16, body: String) -> Value {
+    send_request(port, body).await["result"].clone()
+}
@@ -1,8 +1,12 @@
+pub mod admin_api;
 pub mod alchemy_api;
 pub mod debug_api;
+pub mod eth_filter;
 pub mod eth_provider;
+pub mod eth_pubsub;
 pub mod kakarot_api;
 pub mod mempool;
+pub mod rpc_limits;
 pub mod trace_api;
 pub mod tracer;
 pub mod txpool_api;
@@ -0,0 +1,44 @@
+#![allow(clippy::used_underscore_binding)]
+#![cfg(feature = "testing")]
+use kakarot_rpc::test_utils::{
+    fixtures::{katana_empty, setup},
+    katana::Katana,
+    rpc::{start_kakarot_rpc_server, RawRpcParamsBuilder},
+};
+use rstest::*;
+use serde_json::Value;
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batch_request_limit(#[future] katana_empty: Katana, _setup: ()) {
+    // Given
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana_empty).await.expect("Error setting up Kakarot RPC server");
+    let batch = |len: usize| {
+        let calls: Vec<_> = (0..len).map(|_| RawRpcParamsBuilder::new("eth_chainId").build()).collect();
+        format!("[{}]", calls.join(","))
+    };
+    let reqwest_client = reqwest::Client::new();
+
+    // When
+    let mut responses = Vec::new();
+    for len in [2, 101] {
+        let res = reqwest_client
+            .post(format!("http://localhost:{}", server_addr.port()))
+            .header("Content-Type", "application/json")
+            .body(batch(len))
+            .send()
+            .await
+            .expect("Failed to call batch");
+        let response: Value = res.json().await.expect("Failed to deserialize response body");
+        responses.push(response);
+    }
+
+    // Then
+    assert_eq!(responses[0].as_array().map(Vec::len), Some(2));
+    assert_eq!(responses[1]["error"]["code"], -32010);
+    assert_eq!(responses[1]["id"], Value::Null);
+
+    drop(server_handle);
+}
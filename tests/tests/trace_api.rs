@@ -5,9 +5,13 @@ use alloy_dyn_abi::DynSolValue;
 use alloy_eips::BlockId;
 use alloy_primitives::{Address, TxKind, B256, U256};
 use alloy_rpc_types::{request::TransactionInput, TransactionRequest};
-use alloy_rpc_types_trace::geth::{
-    CallFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions,
-    GethTrace,
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    geth::{
+        CallFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace,
+    },
+    parity::{Action, LocalizedTransactionTrace, TraceResults, TraceType},
 };
 use alloy_serde::{OtherFields, WithOtherFields};
 use alloy_sol_types::{sol, SolCall};
@@ -18,12 +22,13 @@ use kakarot_rpc::{
         evm_contract::{EvmContract, KakarotEvmContract, TransactionInfo, TxCommonInfo, TxFeeMarketInfo},
         fixtures::{plain_opcodes, setup},
         katana::Katana,
-        rpc::{start_kakarot_rpc_server, RawRpcParamsBuilder},
+        rpc::{send_request, start_kakarot_rpc_server, RawRpcParamsBuilder},
     },
 };
 use rstest::*;
 use serde_json::Value;
 use starknet::{core::types::MaybePendingBlockWithTxHashes, providers::Provider};
+use std::collections::HashSet;
 
 /// The block number on which tracing will be performed.
 const TRACING_BLOCK_NUMBER: u64 = 0x3;
@@ -291,3 +296,155 @@ async fn test_trace_call_counter(#[future] plain_opcodes: (Katana, KakarotEvmCon
     // Clean up by dropping the server handle
     drop(server_handle);
 }
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trace_call_parity(#[future] plain_opcodes: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+    let request = TransactionRequest {
+        from: Some(eoa_address),
+        to: Some(TxKind::Call(Address::ZERO)),
+        gas: Some(21000),
+        value: Some(U256::ZERO),
+        ..Default::default()
+    };
+
+    // When
+    let response = send_request(
+        server_addr.port(),
+        RawRpcParamsBuilder::new("trace_call")
+            .add_param(request)
+            .add_param(HashSet::from([TraceType::Trace]))
+            .add_param(BlockId::Number(TRACING_BLOCK_NUMBER.into()))
+            .build(),
+    )
+    .await;
+
+    // Then
+    let results: TraceResults =
+        serde_json::from_value(response["result"].clone()).expect("Failed to deserialize trace results");
+    assert_eq!(results.trace.len(), 1);
+    assert!(results.state_diff.is_none());
+    let trace = &results.trace[0];
+    assert!(trace.error.is_none());
+    assert!(trace.trace_address.is_empty());
+    assert!(matches!(&trace.action, Action::Call(call) if call.from == eoa_address && call.to == Address::ZERO));
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trace_call_many(#[future] plain_opcodes: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+    let request = TransactionRequest {
+        from: Some(eoa_address),
+        to: Some(TxKind::Call(Address::ZERO)),
+        gas: Some(21000),
+        value: Some(U256::ZERO),
+        ..Default::default()
+    };
+    let calls = vec![
+        (request.clone(), HashSet::from([TraceType::Trace])),
+        (request, HashSet::from([TraceType::Trace, TraceType::StateDiff])),
+    ];
+
+    // When
+    let response = send_request(
+        server_addr.port(),
+        RawRpcParamsBuilder::new("trace_callMany")
+            .add_param(calls)
+            .add_param(BlockId::Number(TRACING_BLOCK_NUMBER.into()))
+            .build(),
+    )
+    .await;
+
+    // Then
+    let results: Vec<TraceResults> =
+        serde_json::from_value(response["result"].clone()).expect("Failed to deserialize trace results");
+    assert_eq!(results.len(), 2);
+    for result in &results {
+        assert_eq!(result.trace.len(), 1);
+        assert!(matches!(&result.trace[0].action, Action::Call(call) if call.from == eoa_address));
+    }
+    // Each call only returns the requested trace types.
+    assert!(results[0].state_diff.is_none());
+    assert!(results[1].state_diff.is_some());
+
+    drop(server_handle);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trace_filter(#[future] plain_opcodes: (Katana, KakarotEvmContract), _setup: ()) {
+    // Given
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+    let (server_addr, server_handle) =
+        start_kakarot_rpc_server(&katana).await.expect("Error setting up Kakarot RPC server");
+    let port = server_addr.port();
+
+    let eoa_address = katana.eoa().evm_address().expect("Failed to get eoa address");
+    let contract_address = Address::from_slice(&plain_opcodes.evm_address.to_bytes_be()[12..]);
+    let filter = TraceFilter {
+        from_block: Some(TRACING_BLOCK_NUMBER),
+        to_block: Some(TRACING_BLOCK_NUMBER),
+        from_address: vec![eoa_address],
+        to_address: vec![contract_address],
+        ..Default::default()
+    };
+
+    // When
+    let response = send_request(port, RawRpcParamsBuilder::new("trace_filter").add_param(&filter).build()).await;
+    let paginated_response = send_request(
+        port,
+        RawRpcParamsBuilder::new("trace_filter")
+            .add_param(TraceFilter { after: Some(1), count: Some(2), ..filter.clone() })
+            .build(),
+    )
+    .await;
+    let too_large_response = send_request(
+        port,
+        RawRpcParamsBuilder::new("trace_filter")
+            .add_param(TraceFilter { from_block: Some(0), to_block: Some(100), ..filter })
+            .build(),
+    )
+    .await;
+
+    // Then
+    // Only the top level calls of the transactions which aren't out of resources match the filter.
+    let traces: Vec<LocalizedTransactionTrace> =
+        serde_json::from_value(response["result"].clone()).expect("Failed to deserialize traces");
+    assert_eq!(traces.len(), TRACING_TRANSACTIONS_COUNT - 1);
+    for trace in &traces {
+        assert_eq!(trace.block_number, Some(TRACING_BLOCK_NUMBER));
+        assert!(trace.trace.trace_address.is_empty());
+        assert!(matches!(&trace.trace.action, Action::Call(call) if call.to == contract_address));
+    }
+
+    let paginated_traces: Vec<LocalizedTransactionTrace> =
+        serde_json::from_value(paginated_response["result"].clone()).expect("Failed to deserialize traces");
+    assert_eq!(paginated_traces, traces[1..3]);
+
+    assert_eq!(too_large_response["error"]["message"], "block range too large; currently limited to 100 blocks");
+
+    drop(server_handle);
+}
@@ -2,10 +2,17 @@
 #![cfg(feature = "testing")]
 use alloy_consensus::Transaction;
 use alloy_dyn_abi::DynSolValue;
-use alloy_primitives::{Address, Bytes, B256, B64, U256};
+use alloy_primitives::{Address, Bytes, TxKind, B256, B64, U256};
+use alloy_rpc_types::{
+    state::{AccountOverride, StateOverride},
+    TransactionRequest,
+};
 use alloy_rpc_types_trace::{
-    geth::{GethDebugTracingOptions, GethTrace, TraceResult},
-    parity::{Action, CallAction, CallOutput, CallType, TraceOutput, TransactionTrace},
+    geth::{
+        CallFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+        GethDebugTracingOptions, GethTrace, PreStateFrame, TraceResult,
+    },
+    parity::{Action, CallAction, CallOutput, CallType, TraceOutput, TraceType, TransactionTrace},
 };
 use alloy_serde::{OtherFields, WithOtherFields};
 use kakarot_rpc::{
@@ -22,7 +29,7 @@ use revm_inspectors::tracing::TracingInspectorConfig;
 use rstest::*;
 use serde_json::json;
 use starknet::{core::types::MaybePendingBlockWithTxHashes, providers::Provider};
-use std::sync::Arc;
+use std::collections::HashSet;
 
 /// The block number on which tracing will be performed.
 const TRACING_BLOCK_NUMBER: u64 = 0x3;
@@ -144,7 +151,7 @@ async fn test_trace_block(#[future] plain_opcodes: (Katana, KakarotEvmContract),
 
     // Create a new TracerBuilder instance.
     let tracer_builder_block =
-        TracerBuilder::new(Arc::new(&eth_provider)).await.expect("Failed to create tracer_builder_block");
+        TracerBuilder::new(eth_provider.clone()).await.expect("Failed to create tracer_builder_block");
     let tracer = tracer_builder_block
         .with_block_id(TRACING_BLOCK_NUMBER.into())
         .await
@@ -210,7 +217,7 @@ async fn test_debug_trace_block_by_number(#[future] plain_opcodes: (Katana, Kaka
     let eth_provider = katana.eth_provider();
     // Create a new TracerBuilder instance.
     let tracer_builder_block =
-        TracerBuilder::new(Arc::new(&eth_provider)).await.expect("Failed to create tracer_builder_block");
+        TracerBuilder::new(eth_provider.clone()).await.expect("Failed to create tracer_builder_block");
 
     // Get the traces for the block
     let block_trace = tracer_builder_block
@@ -259,7 +266,7 @@ async fn test_debug_trace_transaction(#[future] plain_opcodes: (Katana, KakarotE
     // Get the Ethereum provider from the Katana instance.
     let eth_provider = katana.eth_provider();
     // Create a TracerBuilder instance
-    let tracer_builder = TracerBuilder::new(Arc::new(&eth_provider)).await.expect("Failed to create tracer_builder");
+    let tracer_builder = TracerBuilder::new(eth_provider.clone()).await.expect("Failed to create tracer_builder");
 
     // Get the traces for the tx.
     let trace_with_tx_hash = tracer_builder
@@ -303,3 +310,299 @@ async fn test_debug_trace_transaction(#[future] plain_opcodes: (Katana, KakarotE
         TraceResult::Error { .. } => panic!("Expected a success trace result"),
     };
 }
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_trace_transaction(#[future] plain_opcodes: (Katana, KakarotEvmContract), _setup: ()) {
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+
+    // Get the block in order to trace a transaction.
+    let block = katana
+        .eth_provider()
+        .block_by_number(TRACING_BLOCK_NUMBER.into(), false)
+        .await
+        .expect("Failed to get block")
+        .unwrap();
+
+    let index = TRACING_TRANSACTIONS_COUNT - 2;
+    let tx_hash = block.transactions.as_hashes().unwrap().get(index).unwrap();
+
+    // Get the Ethereum provider from the Katana instance.
+    let eth_provider = katana.eth_provider();
+    // Create a TracerBuilder instance
+    let tracer_builder = TracerBuilder::new(eth_provider.clone()).await.expect("Failed to create tracer_builder");
+
+    // Get the traces for the tx.
+    let traces = tracer_builder
+        .clone()
+        .with_transaction_hash(*tx_hash)
+        .await
+        .expect("Failed to set transaction hash")
+        .with_tracing_options(TracingInspectorConfig::default_parity().into())
+        .build()
+        .expect("Failed to build tracer")
+        .trace_transaction(*tx_hash)
+        .expect("Failed to trace transaction")
+        .unwrap_or_default();
+
+    // Get the traces for the block
+    let block_traces = tracer_builder
+        .with_block_id(TRACING_BLOCK_NUMBER.into())
+        .await
+        .expect("Failed to set block number")
+        .with_tracing_options(TracingInspectorConfig::default_parity().into())
+        .build()
+        .expect("Failed to build tracer")
+        .trace_block()
+        .expect("Failed to trace block")
+        .unwrap_or_default();
+
+    // Compare the traces of the transaction with the ones of the block
+    let expected_traces: Vec<_> =
+        block_traces.into_iter().filter(|trace| trace.transaction_hash == Some(*tx_hash)).collect();
+    // We expect 3 traces for the transaction: CALL, CREATE, and CALL.
+    assert_eq!(traces.len(), 3);
+    assert_eq!(traces, expected_traces);
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_block(#[future] plain_opcodes: (Katana, KakarotEvmContract), _setup: ()) {
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+
+    // Get the Ethereum provider from the Katana instance.
+    let eth_provider = katana.eth_provider();
+    let trace_types = HashSet::from([TraceType::Trace, TraceType::StateDiff]);
+
+    // When
+    let results = TracerBuilder::new(eth_provider.clone())
+        .await
+        .expect("Failed to create tracer_builder")
+        .with_block_id(TRACING_BLOCK_NUMBER.into())
+        .await
+        .expect("Failed to set block number")
+        .build()
+        .expect("Failed to build tracer")
+        .replay_block(&trace_types)
+        .expect("Failed to replay block");
+
+    // Then
+    assert_eq!(results.len(), TRACING_TRANSACTIONS_COUNT);
+    // We expect 3 traces per transaction: CALL, CREATE, and CALL, and a state diff.
+    for result in &results[..TRACING_TRANSACTIONS_COUNT - 1] {
+        assert_eq!(result.full_trace.trace.len(), 3);
+        assert!(result.full_trace.state_diff.as_ref().is_some_and(|diff| !diff.0.is_empty()));
+        assert!(result.full_trace.vm_trace.is_none());
+    }
+
+    // The last transaction is out of resources and has a single default trace.
+    let out_of_resources = &results.last().unwrap().full_trace;
+    assert_eq!(out_of_resources.trace.len(), 1);
+    assert!(out_of_resources.state_diff.is_none());
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_debug_trace_transaction_built_in_tracers(
+    #[future] plain_opcodes: (Katana, KakarotEvmContract),
+    _setup: (),
+) {
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+
+    // Get the block in order to trace a transaction.
+    let block = katana
+        .eth_provider()
+        .block_by_number(TRACING_BLOCK_NUMBER.into(), false)
+        .await
+        .expect("Failed to get block")
+        .unwrap();
+    let tx_hash = *block.transactions.as_hashes().unwrap().first().unwrap();
+
+    // Get the Ethereum provider from the Katana instance.
+    let eth_provider = katana.eth_provider();
+    let tracer_builder = TracerBuilder::new(eth_provider.clone()).await.expect("Failed to create tracer_builder");
+
+    let tracers = [
+        json!({ "tracer": "4byteTracer" }),
+        json!({ "tracer": "noopTracer" }),
+        json!({ "tracer": "prestateTracer" }),
+        json!({ "tracer": "prestateTracer", "tracerConfig": { "diffMode": true } }),
+        json!({ "tracer": "flatCallTracer" }),
+        json!({ "tracer": "muxTracer", "tracerConfig": { "4byteTracer": {}, "callTracer": {} } }),
+    ];
+
+    for opts in tracers {
+        let opts: GethDebugTracingOptions =
+            serde_json::from_value(opts).expect("Failed to deserialize tracing options");
+
+        // When
+        let trace = tracer_builder
+            .clone()
+            .with_transaction_hash(tx_hash)
+            .await
+            .expect("Failed to set transaction hash")
+            .with_tracing_options(kakarot_rpc::tracing::builder::TracingOptions::Geth(opts.clone()))
+            .build()
+            .expect("Failed to build tracer")
+            .debug_transaction(tx_hash)
+            .expect("Failed to trace transaction");
+
+        // Then
+        match trace {
+            GethTrace::FourByteTracer(frame) => assert!(!frame.0.is_empty()),
+            GethTrace::NoopTracer(_) => {}
+            GethTrace::PreStateTracer(PreStateFrame::Default(mode)) => assert!(!mode.0.is_empty()),
+            GethTrace::PreStateTracer(PreStateFrame::Diff(mode)) => assert!(!mode.post.is_empty()),
+            // We expect 3 traces: CALL, CREATE, and CALL.
+            GethTrace::FlatCallTracer(traces) => assert_eq!(traces.len(), 3),
+            GethTrace::MuxTracer(frame) => assert_eq!(frame.0.len(), 2),
+            trace => panic!("Unexpected trace {trace:?} for options {opts:?}"),
+        }
+    }
+}
+
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_debug_transaction_request_with_state_overrides(
+    #[future] plain_opcodes: (Katana, KakarotEvmContract),
+    _setup: (),
+) {
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+
+    // Given
+    // The sender has no balance, the state overrides are needed to transfer the value.
+    let from = Address::repeat_byte(0x11);
+    let value = U256::from(1_000);
+    let request = TransactionRequest {
+        from: Some(from),
+        to: Some(TxKind::Call(Address::ZERO)),
+        value: Some(value),
+        gas: Some(21_000),
+        ..Default::default()
+    };
+    let mut state_overrides = StateOverride::default();
+    state_overrides.insert(from, AccountOverride { balance: Some(value), ..Default::default() });
+    let opts = GethDebugTracingCallOptions {
+        tracing_options: GethDebugTracingOptions::default()
+            .with_tracer(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer)),
+        state_overrides: Some(state_overrides),
+        block_overrides: None,
+    };
+
+    // When
+    let eth_provider = katana.eth_provider();
+    let trace = TracerBuilder::new(eth_provider.clone())
+        .await
+        .expect("Failed to create tracer_builder")
+        .with_block_id(TRACING_BLOCK_NUMBER.into())
+        .await
+        .expect("Failed to set block number")
+        .with_tracing_options(opts.into())
+        .build_for_call()
+        .debug_transaction_request(&request)
+        .expect("Failed to trace transaction request");
+
+    // Then
+    assert_eq!(
+        trace,
+        GethTrace::CallTracer(CallFrame {
+            from,
+            gas: U256::from(21_000),
+            gas_used: U256::from(21_000),
+            to: Some(Address::ZERO),
+            value: Some(value),
+            typ: "CALL".to_string(),
+            ..Default::default()
+        })
+    );
+}
+
+#[cfg(feature = "js-tracer")]
+#[rstest]
+#[awt]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_debug_trace_transaction_js_tracer(#[future] plain_opcodes: (Katana, KakarotEvmContract), _setup: ()) {
+    use std::time::{Duration, Instant};
+
+    let katana = plain_opcodes.0;
+    let plain_opcodes = plain_opcodes.1;
+    tracing(&katana, &plain_opcodes, "createCounterAndInvoke", Box::new(|_| vec![])).await;
+
+    // Get the block in order to trace a transaction.
+    let block = katana
+        .eth_provider()
+        .block_by_number(TRACING_BLOCK_NUMBER.into(), false)
+        .await
+        .expect("Failed to get block")
+        .unwrap();
+    let tx_hash = *block.transactions.as_hashes().unwrap().first().unwrap();
+
+    // Get the Ethereum provider from the Katana instance.
+    let eth_provider = katana.eth_provider();
+    let tracer_builder = TracerBuilder::new(eth_provider.clone()).await.expect("Failed to create tracer_builder");
+
+    // A tracer counting the executed opcodes, one whose step function is slower than its timeout and
+    // one whose step function loops until the loop iteration limit of the JS engine stops it, so that
+    // its thread returns after the timeout instead of running forever.
+    let counter: GethDebugTracingOptions = serde_json::from_value(json!({
+        "tracer": concat!(
+            "{count: 0, step: function() { this.count++ }, ",
+            "fault: function() {}, result: function() { return this.count }}"
+        )
+    }))
+    .expect("Failed to deserialize tracing options");
+    let timed_out: GethDebugTracingOptions = serde_json::from_value(json!({
+        "tracer": concat!(
+            "{step: function() { var now = Date.now(); while (Date.now() - now < 50) {} }, ",
+            "fault: function() {}, result: function() { return null }}"
+        ),
+        "timeout": "10ms"
+    }))
+    .expect("Failed to deserialize tracing options");
+    let infinite_loop: GethDebugTracingOptions = serde_json::from_value(json!({
+        "tracer": concat!(
+            "{step: function() { for (;;) {} }, ",
+            "fault: function() {}, result: function() { return null }}"
+        ),
+        "timeout": "1ms"
+    }))
+    .expect("Failed to deserialize tracing options");
+
+    // When
+    let trace = |opts: GethDebugTracingOptions| {
+        let tracer_builder = tracer_builder.clone();
+        async move {
+            tracer_builder
+                .with_transaction_hash(tx_hash)
+                .await
+                .expect("Failed to set transaction hash")
+                .with_tracing_options(kakarot_rpc::tracing::builder::TracingOptions::Geth(opts))
+                .build()
+                .expect("Failed to build tracer")
+                .debug_transaction(tx_hash)
+        }
+    };
+    let count = trace(counter).await.expect("Failed to trace transaction");
+    let timeout = trace(timed_out).await;
+    let start = Instant::now();
+    let infinite_loop = trace(infinite_loop).await;
+    let infinite_loop_duration = start.elapsed();
+
+    // Then
+    assert!(matches!(count, GethTrace::JS(count) if count.as_u64().is_some_and(|count| count > 0)));
+    assert!(timeout.unwrap_err().to_string().contains("execution timeout"));
+    assert!(infinite_loop.unwrap_err().to_string().contains("execution timeout"));
+    assert!(infinite_loop_duration < Duration::from_secs(1));
+}